@@ -0,0 +1,203 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, spanned::Spanned, Attribute, Data, DeriveInput, Expr, ExprLit, Fields, Lit,
+    Meta,
+};
+
+///Derives `manodae::token::TokenKind` and a matching `Display` for a token enum.
+///
+///- `#[eof]` marks the end of file variant, `#[error]` the error variant. Both
+///  must be unit variants. As Logos rejects a bare `#[error]` on a variant,
+///  `#[token_kind(eof)]` and `#[token_kind(error)]` are accepted as well.
+///- every variant gets a terminal id in declaration order, its terminal name is
+///  the variant name (`EOF` for the end of file variant)
+///- `#[display = "'+'"]` sets the name shown for the variant in `Display` and in
+///  "Expected ..." messages, defaults to the terminal name
+///- unit variants are the tokens inserted by applied repairs, variants
+///  carrying data are never inserted
+#[proc_macro_derive(TokenKind, attributes(eof, error, display, token_kind))]
+pub fn derive_token_kind(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match token_kind(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct Variant {
+    pattern: TokenStream2,
+    //constructor of a unit variant
+    unit: Option<TokenStream2>,
+    terminal_name: String,
+    display_name: String,
+}
+
+#[derive(Default)]
+struct VariantAttributes {
+    eof: bool,
+    error: bool,
+    display: Option<String>,
+}
+
+fn token_kind(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(
+            input.ident.span(),
+            "TokenKind can only be derived for enums",
+        ));
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let mut variants: Vec<Variant> = vec![];
+    let mut eof: Option<TokenStream2> = None;
+    let mut error: Option<TokenStream2> = None;
+    for variant in data.variants.iter() {
+        let ident = &variant.ident;
+        let attributes = variant_attributes(&variant.attrs)?;
+        let pattern = match &variant.fields {
+            Fields::Unit => quote! {#name::#ident},
+            Fields::Unnamed(_) => quote! {#name::#ident(..)},
+            Fields::Named(_) => quote! {#name::#ident{..}},
+        };
+        for (marked, slot, attribute) in [
+            (attributes.eof, &mut eof, "eof"),
+            (attributes.error, &mut error, "error"),
+        ] {
+            if !marked {
+                continue;
+            }
+            if !matches!(variant.fields, Fields::Unit) {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    format!("#[{attribute}] variant must be a unit variant"),
+                ));
+            }
+            if slot.is_some() {
+                return Err(syn::Error::new(
+                    variant.span(),
+                    format!("only one variant can be marked #[{attribute}]"),
+                ));
+            }
+            *slot = Some(pattern.clone());
+        }
+        let terminal_name = if attributes.eof {
+            String::from("EOF")
+        } else {
+            ident.to_string()
+        };
+        let display_name = attributes.display.unwrap_or(terminal_name.clone());
+        let unit = matches!(variant.fields, Fields::Unit).then(|| quote! {#name::#ident});
+        variants.push(Variant {
+            pattern,
+            unit,
+            terminal_name,
+            display_name,
+        });
+    }
+    let eof = eof.ok_or(syn::Error::new(
+        input.ident.span(),
+        "TokenKind needs a variant marked #[eof]",
+    ))?;
+    let error = error.ok_or(syn::Error::new(
+        input.ident.span(),
+        "TokenKind needs a variant marked #[error]",
+    ))?;
+
+    let ids = variants.iter().enumerate().map(|(id, variant)| {
+        let pattern = &variant.pattern;
+        quote! {#pattern => #id}
+    });
+    let displays = variants.iter().map(|variant| {
+        let pattern = &variant.pattern;
+        let display_name = &variant.display_name;
+        quote! {#pattern => #display_name}
+    });
+    let repair_tokens = variants.iter().enumerate().filter_map(|(id, variant)| {
+        let unit = variant.unit.as_ref()?;
+        Some(quote! {#id => Some(#unit)})
+    });
+    let terminal_names = variants.iter().map(|variant| &variant.terminal_name);
+    let display_names = variants.iter().map(|variant| &variant.display_name);
+
+    Ok(quote! {
+        impl #impl_generics ::manodae::token::TokenKind for #name #ty_generics #where_clause {
+            type TokenKind = Self;
+            fn error() -> Self::TokenKind {
+                #error
+            }
+            fn eof() -> Self::TokenKind {
+                #eof
+            }
+            fn terminal_id(&self) -> usize {
+                match self {
+                    #(#ids),*
+                }
+            }
+            fn terminal_names() -> &'static [&'static str] {
+                &[#(#terminal_names),*]
+            }
+            fn terminal_display_names() -> &'static [&'static str] {
+                &[#(#display_names),*]
+            }
+            fn repair_token(terminal_id: usize) -> Option<Self> {
+                match terminal_id {
+                    #(#repair_tokens,)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #ty_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(match self {
+                    #(#displays),*
+                })
+            }
+        }
+    })
+}
+
+fn variant_attributes(attrs: &[Attribute]) -> syn::Result<VariantAttributes> {
+    let mut attributes = VariantAttributes::default();
+    for attr in attrs.iter() {
+        if attr.path().is_ident("eof") {
+            attributes.eof = true;
+        } else if attr.path().is_ident("error") {
+            attributes.error = true;
+        } else if attr.path().is_ident("display") {
+            attributes.display = Some(display_name(&attr.meta)?);
+        } else if attr.path().is_ident("token_kind") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("eof") {
+                    attributes.eof = true;
+                    Ok(())
+                } else if meta.path.is_ident("error") {
+                    attributes.error = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `eof` or `error`"))
+                }
+            })?;
+        }
+    }
+    Ok(attributes)
+}
+
+fn display_name(meta: &Meta) -> syn::Result<String> {
+    if let Meta::NameValue(name_value) = meta {
+        if let Expr::Lit(ExprLit {
+            lit: Lit::Str(display),
+            ..
+        }) = &name_value.value
+        {
+            return Ok(display.value());
+        }
+    }
+    Err(syn::Error::new(
+        meta.span(),
+        "expected #[display = \"...\"]",
+    ))
+}
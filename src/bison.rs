@@ -0,0 +1,147 @@
+use std::{fs::File, io::Write, path::Path};
+
+use indexmap::IndexMap;
+
+use crate::{
+    grammar::Grammar,
+    interner::Interner,
+    precedence::Associativity,
+    production::{ProductionId, AUGMENT_PRODUCTION_ID},
+    symbol::{Symbol, SymbolId, EOF_SYMBOL_ID, ERROR_SYMBOL_ID},
+};
+
+//Exports a grammar as a Bison .y file so that the conflicts and state counts
+//of construct_LALR_Table can be cross-checked against `bison -v` output.
+//Semantic actions are not translated, they are written as comments.
+impl<AST, Token, TranslatorStack> Grammar<AST, Token, TranslatorStack> {
+    pub fn to_bison(&self) -> String {
+        let names = bison_names(&self.symbols.vec);
+        let mut out = String::new();
+
+        out.push_str("/* generated by manodae, semantic actions are replaced by comments */\n\n");
+        for terminal in self.symbols.terminals.iter() {
+            if [EOF_SYMBOL_ID, ERROR_SYMBOL_ID].contains(terminal) {
+                //bison adds its own end marker and error token
+                continue;
+            }
+            let name = self.symbols.lookup(*terminal).to_string();
+            out.push_str(&format!(
+                "%token {} \"{}\"\n",
+                names[terminal],
+                escape_string(&name)
+            ));
+        }
+
+        //one declaration per level, lowest first like in yacc
+        let mut levels: Vec<(usize, Associativity)> = self
+            .precedences
+            .terminals
+            .values()
+            .map(|precedence| (precedence.level, precedence.associativity))
+            .collect();
+        levels.sort_by_key(|(level, _)| *level);
+        levels.dedup_by_key(|(level, _)| *level);
+        if !levels.is_empty() {
+            out.push('\n');
+        }
+        for (level, associativity) in levels {
+            let terminals: Vec<String> = self
+                .precedences
+                .terminals
+                .iter()
+                .filter(|(_, precedence)| precedence.level == level)
+                .map(|(terminal, _)| names[terminal].clone())
+                .collect();
+            let declaration = match associativity {
+                Associativity::Left => "%left",
+                Associativity::Right => "%right",
+                Associativity::NonAssoc => "%nonassoc",
+            };
+            out.push_str(&format!("{declaration} {}\n", terminals.join(" ")));
+        }
+
+        let augment_production = &self.productions.vec[AUGMENT_PRODUCTION_ID.0];
+        if let Some(start) = augment_production.body.first() {
+            out.push_str(&format!("\n%start {}\n", names[start]));
+        }
+
+        out.push_str("\n%%\n\n");
+        for production in self.productions.vec.iter() {
+            if production.is_augmented_production() {
+                continue;
+            }
+            let body = if production.body.is_empty() {
+                String::from("%empty")
+            } else {
+                production
+                    .body
+                    .iter()
+                    .map(|symbol| names[symbol].clone())
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            };
+            out.push_str(&format!("{}: {}", names[&production.head], body));
+            if let Some(terminal) = self
+                .precedences
+                .productions
+                .get(&ProductionId(production.index))
+            {
+                out.push_str(&format!(" %prec {}", names[terminal]));
+            }
+            if production.predicate.is_some() {
+                out.push_str(&format!(
+                    " /* predicate {} */",
+                    escape_comment(&production.predicate_tokens.to_string())
+                ));
+            }
+            if production.action.is_some() {
+                out.push_str(&format!(
+                    " /* {} */",
+                    escape_comment(&production.action_tokens.to_string())
+                ));
+            }
+            out.push_str("\n    ;\n");
+        }
+        out.push_str("\n%%\n");
+        out
+    }
+
+    pub fn write_bison<P: AsRef<Path>>(&self, path: P) -> std::io::Result<()> {
+        let mut file = File::create(path)?;
+        file.write_all(self.to_bison().as_bytes())
+    }
+}
+
+//Bison identifiers are restricted to letters, digits, '_' and '.', grammar
+//symbols are not (ex: S', or terminals named after their lexeme like "+")
+fn bison_names(symbols: &[Symbol]) -> IndexMap<SymbolId, String> {
+    let mut names: IndexMap<SymbolId, String> = IndexMap::new();
+    for (index, symbol) in symbols.iter().enumerate() {
+        if SymbolId(index) == ERROR_SYMBOL_ID {
+            names.insert(ERROR_SYMBOL_ID, String::from("error"));
+            continue;
+        }
+        let prefix = if symbol.is_terminal() { "T_" } else { "N_" };
+        let mut name: String = symbol
+            .to_string()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) || name == "error" {
+            name = format!("{prefix}{name}");
+        }
+        if names.values().any(|existing| *existing == name) {
+            name = format!("{name}_{index}");
+        }
+        names.insert(SymbolId(index), name);
+    }
+    names
+}
+
+fn escape_string(string: &str) -> String {
+    string.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_comment(comment: &str) -> String {
+    comment.replace("*/", "* /")
+}
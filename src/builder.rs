@@ -0,0 +1,298 @@
+use std::sync::Arc;
+
+use indexmap::{IndexMap, IndexSet};
+use quote::quote;
+
+use crate::{
+    context::ActionContext,
+    error::GrammarError,
+    grammar::Grammar,
+    interner::Interner,
+    precedence::Associativity,
+    predicate::{semantic_predicate, Predicate, PredicateContext},
+    production::{semantic_action, Production, SemanticAction, ValueAction},
+    symbol::{Symbol, SymbolId, ERROR_SYMBOL_ID, START_SYMBOL_ID},
+    token::{TerminalName, TokenKind},
+    value::SemanticValue,
+};
+
+///Builds a grammar at runtime without the grammar! macro, ex: from a table of
+///operators.
+///
+///```ignore
+///let grammar = GrammarBuilder::new()
+///    .terminal("Plus")
+///    .terminal("Number")
+///    .left(["Plus"])
+///    .rule("Start").seq(["Expr"])
+///    .rule("Expr").seq(["Expr", "Plus", "Expr"]).action(add)
+///    .rule("Expr").seq(["Number"])
+///    .build()?;
+///```
+///
+///Names passed to `seq` are terminals when declared with `terminal`, every
+///other name is a nonterminal, `error` is the reserved terminal of error
+///productions. The start rule is named `Start` as in grammar!.
+///Actions are runtime closures, they are not carried over by Codegen.
+pub struct GrammarBuilder<AST, Token, TranslatorStack> {
+    terminals: IndexSet<String>,
+    precedences: Vec<(Associativity, Vec<String>)>,
+    rules: Vec<Rule<AST, Token, TranslatorStack>>,
+    head: Option<String>,
+    errors: Vec<GrammarError>,
+}
+
+#[allow(clippy::type_complexity)]
+struct Rule<AST, Token, TranslatorStack> {
+    head: String,
+    body: Vec<String>,
+    error_message: Option<String>,
+    action: Option<SemanticAction<AST, Token, TranslatorStack>>,
+    value_action: Option<ValueAction>,
+    predicate: Option<Predicate<AST, Token>>,
+    precedence: Option<String>,
+}
+
+impl<AST, Token, TranslatorStack> Default for GrammarBuilder<AST, Token, TranslatorStack> {
+    fn default() -> Self {
+        GrammarBuilder {
+            terminals: IndexSet::new(),
+            precedences: vec![],
+            rules: vec![],
+            head: None,
+            errors: vec![],
+        }
+    }
+}
+
+impl<AST, Token, TranslatorStack> GrammarBuilder<AST, Token, TranslatorStack>
+where
+    AST: Clone,
+    Token: Clone + TokenKind,
+    TranslatorStack: Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///Declares a terminal, by name ("Number") or by token value (Token::Plus)
+    pub fn terminal(mut self, terminal: impl TerminalName) -> Self {
+        self.terminals.insert(terminal.terminal_name());
+        self
+    }
+
+    ///Declares left associative terminals, binding tighter than the ones declared before
+    pub fn left<T: TerminalName>(self, terminals: impl IntoIterator<Item = T>) -> Self {
+        self.precedence(Associativity::Left, terminals)
+    }
+
+    ///Declares right associative terminals, binding tighter than the ones declared before
+    pub fn right<T: TerminalName>(self, terminals: impl IntoIterator<Item = T>) -> Self {
+        self.precedence(Associativity::Right, terminals)
+    }
+
+    ///Declares non associative terminals, binding tighter than the ones declared before
+    pub fn nonassoc<T: TerminalName>(self, terminals: impl IntoIterator<Item = T>) -> Self {
+        self.precedence(Associativity::NonAssoc, terminals)
+    }
+
+    pub fn precedence<T: TerminalName>(
+        mut self,
+        associativity: Associativity,
+        terminals: impl IntoIterator<Item = T>,
+    ) -> Self {
+        let terminals = terminals
+            .into_iter()
+            .map(|terminal| terminal.terminal_name())
+            .collect();
+        self.precedences.push((associativity, terminals));
+        self
+    }
+
+    ///Starts the alternatives of a nonterminal, each `seq` that follows adds one
+    pub fn rule(mut self, head: &str) -> Self {
+        self.head = Some(head.to_string());
+        self
+    }
+
+    ///Adds an alternative to the current rule
+    pub fn seq<S: AsRef<str>>(mut self, body: impl IntoIterator<Item = S>) -> Self {
+        let Some(head) = self.head.clone() else {
+            self.errors
+                .push(GrammarError::InvalidBuilderCall(String::from(
+                    "seq() called before rule()",
+                )));
+            return self;
+        };
+        self.rules.push(Rule {
+            head,
+            body: body
+                .into_iter()
+                .map(|symbol| symbol.as_ref().to_string())
+                .collect(),
+            error_message: None,
+            action: None,
+            value_action: None,
+            predicate: None,
+            precedence: None,
+        });
+        self
+    }
+
+    ///Sets the semantic action of the last alternative
+    pub fn action<F>(self, action: F) -> Self
+    where
+        F: Fn(&mut ActionContext<AST, Token, TranslatorStack>) + Send + Sync + 'static,
+    {
+        self.last_rule("action", |rule| rule.action = Some(semantic_action(action)))
+    }
+
+    ///Sets the typed action of the last alternative, it receives the semantic
+    ///values of the body and returns the value of the head. Its Err is
+    ///reported as a semantic error, as take_value's are.
+    pub fn value_action<F>(self, value_action: F) -> Self
+    where
+        F: Fn(Vec<SemanticValue>) -> Result<SemanticValue, String> + Send + Sync + 'static,
+    {
+        self.last_rule("value_action", |rule| {
+            rule.value_action = Some(Arc::new(value_action))
+        })
+    }
+
+    ///Sets the predicate of the last alternative, when it fails the parser
+    ///takes another path or reports a syntax error
+    pub fn predicate<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&PredicateContext<AST, Token>) -> bool + Send + Sync + 'static,
+    {
+        self.last_rule("predicate", |rule| {
+            rule.predicate = Some(semantic_predicate(predicate))
+        })
+    }
+
+    ///Sets the error message reported when the last alternative fails to parse
+    pub fn error_message(self, message: &str) -> Self {
+        self.last_rule("error_message", |rule| {
+            rule.error_message = Some(message.to_string())
+        })
+    }
+
+    ///Gives the last alternative the precedence of a terminal, like %prec in yacc
+    pub fn prec(self, terminal: impl TerminalName) -> Self {
+        self.last_rule("prec", |rule| {
+            rule.precedence = Some(terminal.terminal_name())
+        })
+    }
+
+    fn last_rule(
+        mut self,
+        method: &str,
+        f: impl FnOnce(&mut Rule<AST, Token, TranslatorStack>),
+    ) -> Self {
+        match self.rules.last_mut() {
+            Some(rule) => f(rule),
+            None => self.errors.push(GrammarError::InvalidBuilderCall(format!(
+                "{method}() called before seq()"
+            ))),
+        }
+        self
+    }
+
+    ///Validates the declarations and returns the grammar
+    pub fn build(self) -> Result<Grammar<AST, Token, TranslatorStack>, GrammarError> {
+        if let Some(error) = self.errors.into_iter().next() {
+            return Err(error);
+        }
+        let heads: IndexSet<&String> = self.rules.iter().map(|rule| &rule.head).collect();
+        if !heads.contains(&String::from("Start")) {
+            return Err(GrammarError::MissingStartRule);
+        }
+        let terminal_heads: Vec<String> = heads
+            .iter()
+            .filter(|head| self.terminals.contains(**head))
+            .map(|head| head.to_string())
+            .collect();
+        if !terminal_heads.is_empty() {
+            return Err(GrammarError::TerminalRules(terminal_heads));
+        }
+        let undefined: IndexSet<String> = self
+            .rules
+            .iter()
+            .flat_map(|rule| rule.body.iter())
+            .filter(|symbol| {
+                !self.terminals.contains(*symbol) && !heads.contains(symbol) && *symbol != "error"
+            })
+            .cloned()
+            .collect();
+        if !undefined.is_empty() {
+            return Err(GrammarError::UndefinedNonterminals(
+                undefined.into_iter().collect(),
+            ));
+        }
+        let undeclared: IndexSet<String> = self
+            .precedences
+            .iter()
+            .flat_map(|(_, terminals)| terminals.iter())
+            .chain(
+                self.rules
+                    .iter()
+                    .filter_map(|rule| rule.precedence.as_ref()),
+            )
+            .filter(|terminal| !self.terminals.contains(*terminal))
+            .cloned()
+            .collect();
+        if !undeclared.is_empty() {
+            return Err(GrammarError::UndeclaredTerminals(
+                undeclared.into_iter().collect(),
+            ));
+        }
+
+        let mut grammar = Grammar::new();
+        let mut symbol_ids: IndexMap<String, SymbolId> =
+            IndexMap::from([(String::from("error"), ERROR_SYMBOL_ID)]);
+        for terminal in self.terminals.iter() {
+            let id = grammar.symbols.intern(Symbol::TERMINAL(terminal.clone()));
+            symbol_ids.insert(terminal.clone(), id);
+        }
+        for head in heads.iter() {
+            let id = grammar
+                .symbols
+                .intern(Symbol::NONTERMINAL(head.to_string()));
+            symbol_ids.insert(head.to_string(), id);
+        }
+        debug_assert_eq!(symbol_ids["Start"], START_SYMBOL_ID);
+
+        for (associativity, terminals) in self.precedences.iter() {
+            let terminals: Vec<SymbolId> = terminals
+                .iter()
+                .map(|terminal| symbol_ids[terminal])
+                .collect();
+            grammar.precedences.declare(*associativity, &terminals);
+        }
+
+        for rule in self.rules.into_iter() {
+            let production = Production {
+                head: symbol_ids[&rule.head],
+                body: rule.body.iter().map(|symbol| symbol_ids[symbol]).collect(),
+                error_message: rule.error_message,
+                action: rule.action,
+                action_tokens: quote! {None}.into(),
+                value_action: rule.value_action,
+                value_action_tokens: quote! {None}.into(),
+                argument_types: None,
+                value_type: None,
+                predicate: rule.predicate,
+                predicate_tokens: quote! {None}.into(),
+                index: grammar.productions.vec.len(),
+            };
+            let production_id = grammar.productions.intern(production);
+            if let Some(terminal) = rule.precedence {
+                grammar
+                    .precedences
+                    .productions
+                    .insert(production_id, symbol_ids[&terminal]);
+            }
+        }
+        Ok(grammar)
+    }
+}
@@ -1,7 +1,8 @@
+use indexmap::IndexMap;
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::{action::Action, codegen::ToTokens};
+use crate::{action::Action, codegen::ToTokens, state::StateId, symbol::SymbolId};
 
 impl ToTokens for Action {
     fn to_tokens(&self) -> TokenStream {
@@ -17,9 +18,32 @@ impl ToTokens for Action {
                 quote! {R(#production)}
             }
             //expands to ACTION::ERROR(String)
-            Action::ERROR(err) => quote! {E(String::new(#err))},
+            Action::ERROR(err) => quote! {E(String::from(#err))},
             //expands to ACTION::ACCEPT
             Action::ACCEPT => quote! {A},
         }
     }
 }
+
+//conflicting actions decided by predicates at parse time
+pub(crate) fn predicated_actions(
+    predicated_actions: &IndexMap<StateId, IndexMap<SymbolId, Vec<Action>>>,
+) -> TokenStream {
+    let rows: Vec<TokenStream> = predicated_actions
+        .iter()
+        .map(|(state, row)| {
+            let row: Vec<TokenStream> = row
+                .iter()
+                .map(|(symbol, actions)| {
+                    let symbol = symbol.to_tokens();
+                    let actions: Vec<TokenStream> =
+                        actions.iter().map(|action| action.to_tokens()).collect();
+                    quote! {(#symbol, vec![#(#actions),*])}
+                })
+                .collect();
+            let state = state.to_tokens();
+            quote! {(#state, IndexMap::from([#(#row),*]))}
+        })
+        .collect();
+    quote! {IndexMap::from([#(#rows),*])}
+}
@@ -26,6 +26,39 @@ impl<AST, Token: TokenKind, TranslatorStack> ToTokens for Grammar<AST, Token, Tr
             .collect();
         let productions = self.productions.to_tokens();
 
+        let value_types: Vec<TokenStream> = self
+            .value_types
+            .iter()
+            .map(|(symbol, value_type)| {
+                let symbol = symbol.to_tokens();
+                let value_type = value_type.to_tokens();
+                quote! {(#symbol, #value_type)}
+            })
+            .collect();
+        let precedences = self.precedences.to_tokens();
+        let templates: Vec<TokenStream> = self
+            .templates
+            .iter()
+            .map(|(name, template)| {
+                let template = template.to_tokens();
+                quote! {(f!{#name}, #template)}
+            })
+            .collect();
+        let inline: Vec<TokenStream> = self
+            .inline
+            .iter()
+            .map(|symbol| symbol.to_tokens())
+            .collect();
+
+        let mid_rules: Vec<TokenStream> = self
+            .mid_rules
+            .iter()
+            .map(|(symbol, (left, terminals))| {
+                let symbol = symbol.to_tokens();
+                quote! {(#symbol, (#left, #terminals))}
+            })
+            .collect();
+
         let grammar = quote! {
             let y = vec![#(#productions_vec),*];
             let x = vec![#(#symbols_vec),*];
@@ -33,7 +66,13 @@ impl<AST, Token: TokenKind, TranslatorStack> ToTokens for Grammar<AST, Token, Tr
                 start:#start,
                 productions:#productions,
                 production_head_map:#production_head_map,
-                symbols:#symbols
+                symbols:#symbols,
+                value_types:IndexMap::from([#(#value_types),*]),
+                precedences:#precedences,
+                templates:IndexMap::from([#(#templates),*]),
+                inline:IndexSet::from([#(#inline),*]),
+                mid_rules:IndexMap::from([#(#mid_rules),*]),
+                errors:vec![],
             }
         };
         grammar
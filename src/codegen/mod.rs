@@ -9,7 +9,6 @@ use std::{
 };
 
 use indexmap::{IndexMap, IndexSet};
-use logos::Logos;
 use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 
@@ -26,9 +25,12 @@ mod action;
 mod grammar;
 mod item;
 mod parser;
+mod precedence;
 mod production;
 mod state;
 mod symbol;
+mod template;
+mod value;
 
 pub trait ToTokens {
     fn to_tokens(&self) -> TokenStream;
@@ -52,9 +54,9 @@ const F_HASH: &str = "hash.txt";
 
 impl<AST, Token, TranslatorStack> Codegen<AST, Token, TranslatorStack>
 where
-    AST: Debug + PartialEq + Clone,
-    Token: Debug + PartialEq + Clone + ToString + TokenKind + Logos<'static>,
-    TranslatorStack: Debug + PartialEq + Clone,
+    AST: Debug + PartialEq + Clone + 'static,
+    Token: Debug + PartialEq + Clone + ToString + TokenKind + 'static,
+    TranslatorStack: Debug + PartialEq + Clone + 'static,
 {
     pub fn gen(
         path: PathBuf,
@@ -75,13 +77,20 @@ where
             return;
         }
         let lr = LR1_Parser::new(codegen.grammar.clone());
+        //cargo only sets OUT_DIR for build scripts, which surface warnings
+        //through cargo, elsewhere they stay in the parser's warnings
+        if std::env::var_os("OUT_DIR").is_some() {
+            for warning in lr.warnings.iter() {
+                println!("cargo:warning={warning}");
+            }
+        }
         codegen.write_grammar(lr.grammar.clone());
         codegen.write_LR1_Automata(lr.LR1_automata.clone());
         codegen.write_first_follow_set(lr.first_set.clone(), "first");
         codegen.write_first_follow_set(lr.follow_set.clone(), "follow");
         codegen.write_action(lr.action.clone());
         codegen.write_goto(lr.goto.clone());
-        codegen.write_parser();
+        codegen.write_parser(&lr.terminal_table, &lr.predicated_actions);
         codegen.write_hash(hash);
 
         codegen.rustfmt();
@@ -107,24 +116,18 @@ where
     }
 
     fn mkdir(&mut self) {
-        if self.path.join(FOLDER).exists() {
-            self.path = self.path.join(FOLDER);
-            return;
-        }
-        let mut command = Command::new("mkdir");
-        command.arg(FOLDER);
-        command.current_dir(&self.path);
-        command.spawn().expect("Failed to create codegen folder");
         self.path = self.path.join(FOLDER);
+        fs::create_dir_all(&self.path).expect("Failed to create codegen folder");
     }
 
+    //waits for rustfmt, the files are complete once gen returns
     fn rustfmt(&self) {
         Command::new("rustfmt")
             .args([
                 F_GRAMMAR, F_FIRST, F_FOLLOW, F_LR, F_ACTION, F_GOTO, F_PARSER,
             ])
             .current_dir(&self.path)
-            .spawn()
+            .status()
             .expect("Failed to format generated rust code");
     }
 
@@ -133,8 +136,23 @@ where
         file.write_all(hash.to_string().as_bytes()).ok();
     }
 
-    fn write_parser(&self) {
+    fn write_parser(
+        &self,
+        terminal_table: &[Option<SymbolId>],
+        predicated_actions: &IndexMap<StateId, IndexMap<SymbolId, Vec<Action>>>,
+    ) {
+        let predicated_actions = action::predicated_actions(predicated_actions);
         let [a, t, ts] = [&self.generics[0], &self.generics[1], &self.generics[2]];
+        let terminal_table: Vec<TokenStream> = terminal_table
+            .iter()
+            .map(|symbol| match symbol {
+                Some(symbol) => {
+                    let symbol = symbol.to_tokens();
+                    quote! {Some(#symbol)}
+                }
+                None => quote! {None},
+            })
+            .collect();
         let code = quote! {
             include!(#F_GRAMMAR);
             include!(#F_LR);
@@ -151,9 +169,15 @@ where
                     LR1_automata: __lr__(),
                     follow_set: __follow__(),
                     first_set: __first__(),
+                    //used only when constructing table, no need for parsing
+                    nullable: IndexSet::new(),
                     conflicts: false,
                     goto: __goto__(),
                     action: __action__(),
+                    predicated_actions: #predicated_actions,
+                    terminal_table: vec![#(#terminal_table),*],
+                    warnings: vec![],
+                    repair_input: false,
                     //used only when constructing table, no need for parsing
                     item_closure_map: IndexMap::new(),
                     //used only when constructing table, no need for parsing
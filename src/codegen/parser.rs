@@ -1,7 +1,11 @@
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use crate::{codegen::ToTokens, parser::LR1_Parser, token::TokenKind};
+use crate::{
+    codegen::{action::predicated_actions, ToTokens},
+    parser::LR1_Parser,
+    token::TokenKind,
+};
 
 impl<AST, Token: TokenKind, TranslatorStack> ToTokens for LR1_Parser<AST, Token, TranslatorStack> {
     fn to_tokens(&self) -> TokenStream {
@@ -76,6 +80,18 @@ impl<AST, Token: TokenKind, TranslatorStack> ToTokens for LR1_Parser<AST, Token,
                 quote! {(Rc::new(#key),#value)}
             })
             .collect();
+        let terminal_table: Vec<_> = self
+            .terminal_table
+            .iter()
+            .map(|symbol| match symbol {
+                Some(symbol) => {
+                    let symbol = symbol.to_tokens();
+                    quote! {Some(#symbol)}
+                }
+                None => quote! {None},
+            })
+            .collect();
+        let predicated_actions = predicated_actions(&self.predicated_actions);
         let grammar = self.grammar.to_tokens();
         let parser = quote! {
             L {
@@ -83,11 +99,16 @@ impl<AST, Token: TokenKind, TranslatorStack> ToTokens for LR1_Parser<AST, Token,
                 LR1_automata: #LR1_automata,
                 follow_set: IndexMap::from([#(#follow_set),*]),
                 first_set: IndexMap::from([#(#first_set),*]),
+                nullable: IndexSet::new(),
                 conflicts: false,
                 goto: IndexMap::from([#(#goto),*]),
                 action: IndexMap::from([#(#action),*]),
-                item_closure_map:IndexMap::new(),
-                closure_map:IndexMap::new(),
+                predicated_actions: #predicated_actions,
+                terminal_table: vec![#(#terminal_table),*],
+                warnings: vec![],
+                repair_input: false,
+                item_closure_map: IndexMap::new(),
+                closure_map: IndexMap::new(),
             }
         };
         parser
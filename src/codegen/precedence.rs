@@ -0,0 +1,41 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    codegen::ToTokens,
+    precedence::{Associativity, Precedences},
+};
+
+impl ToTokens for Precedences {
+    fn to_tokens(&self) -> TokenStream {
+        let terminals: Vec<TokenStream> = self
+            .terminals
+            .iter()
+            .map(|(terminal, precedence)| {
+                let terminal = terminal.to_tokens();
+                let level = precedence.level;
+                let associativity = match precedence.associativity {
+                    Associativity::Left => quote! {Associativity::Left},
+                    Associativity::Right => quote! {Associativity::Right},
+                    Associativity::NonAssoc => quote! {Associativity::NonAssoc},
+                };
+                quote! {(#terminal, Precedence { level: #level, associativity: #associativity })}
+            })
+            .collect();
+        let productions: Vec<TokenStream> = self
+            .productions
+            .iter()
+            .map(|(production, terminal)| {
+                let production = production.to_tokens();
+                let terminal = terminal.to_tokens();
+                quote! {(#production, #terminal)}
+            })
+            .collect();
+        quote! {
+            Precedences {
+                terminals: IndexMap::from([#(#terminals),*]),
+                productions: IndexMap::from([#(#productions),*]),
+            }
+        }
+    }
+}
@@ -3,7 +3,7 @@ use quote::quote;
 
 use crate::{
     codegen::ToTokens,
-    production::{Production, ProductionId, Productions},
+    production::{Production, ProductionId, Productions, SourceTokens},
 };
 
 impl<AST, Tokens, TranslatorStack> ToTokens for Production<AST, Tokens, TranslatorStack> {
@@ -16,10 +16,38 @@ impl<AST, Tokens, TranslatorStack> ToTokens for Production<AST, Tokens, Translat
         let action = if self.action_tokens.is_empty() {
             quote! {None}
         } else {
-            self.action_tokens.clone()
+            self.action_tokens.tokens()
+        };
+        let value_action = if self.value_action_tokens.is_empty() {
+            quote! {None}
+        } else {
+            self.value_action_tokens.tokens()
+        };
+        let predicate = if self.predicate_tokens.is_empty() {
+            quote! {None}
+        } else {
+            self.predicate_tokens.tokens()
+        };
+        let action_tokens = self.action_tokens.to_tokens();
+        let value_action_tokens = self.value_action_tokens.to_tokens();
+        let predicate_tokens = self.predicate_tokens.to_tokens();
+        let argument_types = match &self.argument_types {
+            Some(argument_types) => {
+                let argument_types: Vec<TokenStream> = argument_types
+                    .iter()
+                    .map(|argument_type| argument_type.to_tokens())
+                    .collect();
+                quote! {Some(vec![#(#argument_types),*])}
+            }
+            None => quote! {None},
+        };
+        let value_type = match &self.value_type {
+            Some(value_type) => {
+                let value_type = value_type.to_tokens();
+                quote! {Some(#value_type)}
+            }
+            None => quote! {None},
         };
-        //q!{} expands to quote!{}
-        let action_tokens = quote! {quote!{}};
         let body: Vec<_> = self
             .body
             .iter()
@@ -38,12 +66,26 @@ impl<AST, Tokens, TranslatorStack> ToTokens for Production<AST, Tokens, Translat
                 #error_message,
                 #action_tokens,
                 #action,
+                #value_action_tokens,
+                #value_action,
+                #argument_types,
+                #value_type,
+                #predicate_tokens,
+                #predicate,
             )
         };
         production
     }
 }
 
+impl ToTokens for SourceTokens {
+    fn to_tokens(&self) -> TokenStream {
+        //kept as text, parsed back into a TokenStream by the generated code
+        let source = self.to_string();
+        quote! {#source.parse().unwrap()}
+    }
+}
+
 impl ToTokens for ProductionId {
     fn to_tokens(&self) -> proc_macro2::TokenStream {
         let id = self.0;
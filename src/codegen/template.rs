@@ -0,0 +1,91 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::{
+    codegen::ToTokens,
+    template::{Template, TemplateAlternative},
+};
+
+impl<AST, Token, TranslatorStack> ToTokens for Template<AST, Token, TranslatorStack> {
+    fn to_tokens(&self) -> TokenStream {
+        let params = &self.params;
+        //the value types name the parameters, they stand for SemanticValue
+        let aliases: Vec<TokenStream> = params
+            .iter()
+            .map(|param| {
+                let param = format_ident!("{}", param);
+                quote! {
+                    #[allow(non_camel_case_types, dead_code)]
+                    type #param = SemanticValue;
+                }
+            })
+            .collect();
+        let alternatives: Vec<TokenStream> = self
+            .alternatives
+            .iter()
+            .map(|alternative| alternative_tokens(alternative, &aliases))
+            .collect();
+        quote! {
+            Template {
+                params: vec![#(f!{#params}),*],
+                alternatives: vec![#(#alternatives),*],
+            }
+        }
+    }
+}
+
+fn alternative_tokens<AST, Token, TranslatorStack>(
+    alternative: &TemplateAlternative<AST, Token, TranslatorStack>,
+    aliases: &[TokenStream],
+) -> TokenStream {
+    let body: Vec<TokenStream> = alternative
+        .body
+        .iter()
+        .map(|(symbol, args)| quote! {(f!{#symbol}, vec![#(f!{#args}),*])})
+        .collect();
+    let error_message = match &alternative.error_message {
+        Some(message) => quote! {Some(f!{#message})},
+        None => quote! {None},
+    };
+    let source = |tokens: &crate::production::SourceTokens| match tokens.is_empty() {
+        true => quote! {None},
+        false => tokens.tokens(),
+    };
+    let action = source(&alternative.action_tokens);
+    let value_action = source(&alternative.value_action_tokens);
+    let predicate = source(&alternative.predicate_tokens);
+    let action_tokens = alternative.action_tokens.to_tokens();
+    let value_action_tokens = alternative.value_action_tokens.to_tokens();
+    let predicate_tokens = alternative.predicate_tokens.to_tokens();
+    let argument_types = match &alternative.argument_types {
+        Some(argument_types) => {
+            let argument_types: Vec<TokenStream> = argument_types
+                .iter()
+                .map(|argument_type| argument_type.to_tokens())
+                .collect();
+            quote! {Some({ #(#aliases)* vec![#(#argument_types),*] })}
+        }
+        None => quote! {None},
+    };
+    let value_type = match &alternative.value_type {
+        Some(value_type) => {
+            let value_type = value_type.to_tokens();
+            quote! {Some({ #(#aliases)* #value_type })}
+        }
+        None => quote! {None},
+    };
+    quote! {
+        TemplateAlternative {
+            body: vec![#(#body),*],
+            error_message: #error_message,
+            action_tokens: #action_tokens,
+            action: #action,
+            value_action_tokens: #value_action_tokens,
+            value_action: #value_action,
+            argument_types: #argument_types,
+            value_type: #value_type,
+            predicate_tokens: #predicate_tokens,
+            predicate: #predicate,
+        }
+    }
+}
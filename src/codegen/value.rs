@@ -0,0 +1,13 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{codegen::ToTokens, value::ValueType};
+
+impl ToTokens for ValueType {
+    fn to_tokens(&self) -> TokenStream {
+        let name = &self.name;
+        //the TypeId is taken again from the type as written
+        let value_type: TokenStream = name.parse().expect("value types are valid types");
+        quote! {ValueType::of::<#value_type>(#name)}
+    }
+}
@@ -0,0 +1,411 @@
+use indexmap::{IndexMap, IndexSet};
+
+use crate::{
+    error::GrammarError,
+    grammar::Grammar,
+    interner::Interner,
+    production::{Production, ProductionId, Productions},
+    symbol::{Symbol, SymbolId, AUGMENT_START_SYMBOL_ID},
+    token::TokenKind,
+    value::ValueType,
+};
+
+///Ids given to the symbols and productions of an imported grammar
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Import {
+    pub symbols: IndexMap<SymbolId, SymbolId>,
+    pub productions: IndexMap<ProductionId, ProductionId>,
+}
+
+impl Import {
+    ///Follows the renumbering of a `merge_rule` made after the import, the
+    ///productions it removed are dropped
+    pub fn renumber(&mut self, merged: &Merged) {
+        self.productions
+            .retain(|_, id| match merged.renumbered.get(id) {
+                Some(Some(new_id)) => {
+                    *id = *new_id;
+                    true
+                }
+                Some(None) => false,
+                None => true,
+            });
+    }
+}
+
+///Ids given by `merge_rule`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Merged {
+    //ids of the merged alternatives, by their id in the other grammar
+    pub productions: IndexMap<ProductionId, ProductionId>,
+    //productions whose id changed when Merge::Override removed the existing
+    //alternatives, None for the removed ones
+    pub renumbered: IndexMap<ProductionId, Option<ProductionId>>,
+}
+
+///How `merge_rule` treats the alternatives a nonterminal already has
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Merge {
+    //keep them and add the new ones after them
+    Extend,
+    //replace them by the new ones
+    Override,
+}
+
+//Composes grammars that share a token type, ex: an expression grammar reused
+//by several DSLs. Terminals are shared by name, nonterminals and templates of
+//an imported grammar are renamed `namespace::Name` so they can't clash with
+//ours.
+impl<AST, Token, TranslatorStack> Grammar<AST, Token, TranslatorStack>
+where
+    AST: Clone,
+    Token: Clone + TokenKind,
+    TranslatorStack: Clone,
+{
+    ///Imports every production of `other`, its start rule becomes `namespace::Start`.
+    ///Nothing is imported when the grammars conflict.
+    pub fn import(&mut self, other: &Self, namespace: &str) -> Result<Import, GrammarError> {
+        let rename = |symbol: &Symbol| match symbol {
+            Symbol::TERMINAL(_) => symbol.clone(),
+            Symbol::NONTERMINAL(name) => Symbol::NONTERMINAL(format!("{namespace}::{name}")),
+        };
+
+        let mut conflicts = vec![];
+        for head in other.heads() {
+            let renamed = rename(&other.symbols.lookup(head));
+            if let Some(existing) = self.symbols.reverse_lookup(&renamed) {
+                if !self.alternatives(existing).is_empty() {
+                    conflicts.push(format!(
+                        "nonterminal {} is already defined",
+                        renamed.to_string()
+                    ));
+                }
+            }
+        }
+        conflicts.extend(self.precedence_conflicts(other));
+        for name in other.templates.keys() {
+            let renamed = format!("{namespace}::{name}");
+            if self.templates.contains_key(&renamed) {
+                conflicts.push(format!("template {renamed} is already defined"));
+            }
+        }
+        for (head, value_type) in other.value_types.iter() {
+            let renamed = rename(&other.symbols.lookup(*head));
+            conflicts.extend(self.value_type_conflict(&renamed, value_type));
+        }
+        if !conflicts.is_empty() {
+            return Err(GrammarError::ImportConflicts(conflicts));
+        }
+
+        let mut import = Import::default();
+        for (id, symbol) in other.symbols.vec.iter().enumerate() {
+            if SymbolId(id) == AUGMENT_START_SYMBOL_ID {
+                continue;
+            }
+            let new_id = self.symbols.intern(rename(symbol));
+            import.symbols.insert(SymbolId(id), new_id);
+        }
+        self.import_precedences(other, &import.symbols);
+        self.errors.extend(other.errors.iter().cloned());
+        for (head, value_type) in other.value_types.iter() {
+            self.value_types
+                .insert(import.symbols[head], value_type.clone());
+        }
+        for (symbol, left) in other.mid_rules.iter() {
+            self.mid_rules.insert(import.symbols[symbol], *left);
+        }
+        self.inline
+            .extend(other.inline.iter().map(|symbol| import.symbols[symbol]));
+        //the symbols of a template body are renamed like the nonterminals,
+        //its parameters are kept
+        for (name, template) in other.templates.iter() {
+            let mut template = template.clone();
+            let rename = |symbol: &String| match template.params.contains(symbol) {
+                true => symbol.clone(),
+                false => format!("{namespace}::{symbol}"),
+            };
+            let alternatives = template
+                .alternatives
+                .iter()
+                .map(|alternative| {
+                    let mut alternative = alternative.clone();
+                    alternative.body = alternative
+                        .body
+                        .iter()
+                        .map(|(symbol, args)| (rename(symbol), args.iter().map(rename).collect()))
+                        .collect();
+                    alternative
+                })
+                .collect();
+            template.alternatives = alternatives;
+            self.templates
+                .insert(format!("{namespace}::{name}"), template);
+        }
+        for production in other.productions.vec.iter() {
+            if production.is_augmented_production() {
+                continue;
+            }
+            let id = self.add_production(production, import.symbols[&production.head], |symbol| {
+                import.symbols[symbol]
+            });
+            import
+                .productions
+                .insert(ProductionId(production.index), id);
+            if let Some(terminal) = other
+                .precedences
+                .productions
+                .get(&ProductionId(production.index))
+            {
+                self.precedences
+                    .productions
+                    .insert(id, import.symbols[terminal]);
+            }
+        }
+        Ok(import)
+    }
+
+    ///Gives `head` the alternatives of `other`'s `from` rule. Terminals of
+    ///those alternatives are matched by name, nonterminals as
+    ///`namespace::Name`, so `other` is usually imported under `namespace` first.
+    ///Their `%prec` terminals come along with the precedence levels of
+    ///`other`. Overriding renumbers the productions after the removed ones,
+    ///`Import::renumber` keeps an earlier import in step.
+    pub fn merge_rule(
+        &mut self,
+        head: &str,
+        other: &Self,
+        from: &str,
+        namespace: &str,
+        merge: Merge,
+    ) -> Result<Merged, GrammarError> {
+        let head_id = self
+            .symbols
+            .reverse_lookup(&Symbol::NONTERMINAL(head.to_string()))
+            .filter(|head| !self.alternatives(*head).is_empty())
+            .ok_or(GrammarError::UndefinedNonterminals(vec![head.to_string()]))?;
+        let from_id = other
+            .symbols
+            .reverse_lookup(&Symbol::NONTERMINAL(from.to_string()))
+            .filter(|from| !other.alternatives(*from).is_empty())
+            .ok_or(GrammarError::UndefinedNonterminals(vec![from.to_string()]))?;
+
+        let mut symbols: IndexMap<SymbolId, SymbolId> = IndexMap::new();
+        let mut undefined = vec![];
+        for production in other.alternatives(from_id) {
+            for symbol in production.body.iter() {
+                let renamed = match other.symbols.lookup(*symbol) {
+                    terminal @ Symbol::TERMINAL(_) => terminal,
+                    Symbol::NONTERMINAL(name) => {
+                        Symbol::NONTERMINAL(format!("{namespace}::{name}"))
+                    }
+                };
+                match self.symbols.reverse_lookup(&renamed) {
+                    Some(id) => {
+                        symbols.insert(*symbol, id);
+                    }
+                    //new terminals are fine, missing nonterminals would have no rule
+                    None if renamed.is_terminal() => {
+                        symbols.insert(*symbol, self.symbols.intern(renamed));
+                    }
+                    None => undefined.push(renamed.to_string()),
+                }
+            }
+        }
+        if !undefined.is_empty() {
+            return Err(GrammarError::UndefinedNonterminals(undefined));
+        }
+
+        let mut conflicts = self.precedence_conflicts(other);
+        if let Some(value_type) = other.value_types.get(&from_id) {
+            if merge == Merge::Extend {
+                conflicts.extend(
+                    self.value_type_conflict(&Symbol::NONTERMINAL(head.to_string()), value_type),
+                );
+            }
+        }
+        if merge == Merge::Extend {
+            for production in other.alternatives(from_id) {
+                let body: Vec<SymbolId> = production.body.iter().map(|s| symbols[s]).collect();
+                if self.alternatives(head_id).iter().any(|p| p.body == body) {
+                    conflicts.push(format!(
+                        "{head} already has the alternative {}",
+                        body.iter()
+                            .map(|symbol| self.symbols.lookup(*symbol).to_string())
+                            .collect::<Vec<String>>()
+                            .join(" ")
+                    ));
+                }
+            }
+        }
+        if !conflicts.is_empty() {
+            return Err(GrammarError::ImportConflicts(conflicts));
+        }
+
+        let mut merged = Merged::default();
+        if merge == Merge::Override {
+            merged.renumbered = self.remove_alternatives(head_id);
+            self.value_types.shift_remove(&head_id);
+        }
+        if let Some(value_type) = other.value_types.get(&from_id) {
+            self.value_types.insert(head_id, value_type.clone());
+        }
+        //terminals of other's precedences, by name like the body's
+        let terminals: IndexMap<SymbolId, SymbolId> = other
+            .precedences
+            .terminals
+            .keys()
+            .chain(other.precedences.productions.values())
+            .map(|terminal| {
+                (
+                    *terminal,
+                    self.symbols.intern(other.symbols.lookup(*terminal)),
+                )
+            })
+            .collect();
+        self.import_precedences(other, &terminals);
+        for production in other.alternatives(from_id) {
+            let from_production = ProductionId(production.index);
+            let id = self.add_production(&production, head_id, |symbol| symbols[symbol]);
+            if let Some(terminal) = other.precedences.productions.get(&from_production) {
+                self.precedences.productions.insert(id, terminals[terminal]);
+            }
+            merged.productions.insert(from_production, id);
+        }
+        Ok(merged)
+    }
+
+    fn heads(&self) -> IndexSet<SymbolId> {
+        self.productions
+            .vec
+            .iter()
+            .filter(|production| !production.is_augmented_production())
+            .map(|production| production.head)
+            .collect()
+    }
+
+    fn alternatives(&self, head: SymbolId) -> Vec<Production<AST, Token, TranslatorStack>> {
+        self.productions
+            .vec
+            .iter()
+            .filter(|production| production.head == head)
+            .cloned()
+            .collect()
+    }
+
+    fn add_production(
+        &mut self,
+        production: &Production<AST, Token, TranslatorStack>,
+        head: SymbolId,
+        symbol: impl Fn(&SymbolId) -> SymbolId,
+    ) -> ProductionId {
+        let mut production = production.clone();
+        production.head = head;
+        production.body = production.body.iter().map(symbol).collect();
+        production.index = self.productions.vec.len();
+        self.productions.intern(production)
+    }
+
+    //productions are identified by their index, so the ones after a removed
+    //alternative are renumbered. Returns the ids that changed, None for the
+    //removed productions.
+    fn remove_alternatives(
+        &mut self,
+        head: SymbolId,
+    ) -> IndexMap<ProductionId, Option<ProductionId>> {
+        let mut productions = Productions::new();
+        let mut precedences = IndexMap::new();
+        let mut renumbered_ids = IndexMap::new();
+        for production in self.productions.vec.iter().skip(1) {
+            let old_id = ProductionId(production.index);
+            if production.head == head {
+                renumbered_ids.insert(old_id, None);
+                continue;
+            }
+            let mut renumbered = production.clone();
+            renumbered.index = productions.vec.len();
+            let id = productions.intern(renumbered);
+            if id != old_id {
+                renumbered_ids.insert(old_id, Some(id));
+            }
+            if let Some(terminal) = self
+                .precedences
+                .productions
+                .get(&ProductionId(production.index))
+            {
+                precedences.insert(id, *terminal);
+            }
+        }
+        self.productions = productions;
+        self.precedences.productions = precedences;
+        renumbered_ids
+    }
+
+    fn precedence_conflicts(&self, other: &Self) -> Vec<String> {
+        other
+            .precedences
+            .terminals
+            .iter()
+            .filter_map(|(terminal, precedence)| {
+                let symbol = other.symbols.lookup(*terminal);
+                let ours = self.symbols.reverse_lookup(&symbol)?;
+                let ours = self.precedences.terminals.get(&ours)?;
+                (ours.associativity != precedence.associativity).then(|| {
+                    format!(
+                        "terminal {} is declared {:?} and {:?}",
+                        symbol.to_string(),
+                        ours.associativity,
+                        precedence.associativity
+                    )
+                })
+            })
+            .collect()
+    }
+
+    //imported precedence levels are appended after ours, keeping their order
+    fn import_precedences(&mut self, other: &Self, symbols: &IndexMap<SymbolId, SymbolId>) {
+        let mut levels: Vec<usize> = other
+            .precedences
+            .terminals
+            .values()
+            .map(|precedence| precedence.level)
+            .collect();
+        levels.sort();
+        levels.dedup();
+        for level in levels {
+            let terminals: Vec<SymbolId> = other
+                .precedences
+                .terminals
+                .iter()
+                .filter(|(terminal, precedence)| {
+                    precedence.level == level
+                        && !self.precedences.terminals.contains_key(&symbols[*terminal])
+                })
+                .map(|(terminal, _)| symbols[terminal])
+                .collect();
+            if let Some(associativity) = other
+                .precedences
+                .terminals
+                .values()
+                .find(|precedence| precedence.level == level)
+                .map(|precedence| precedence.associativity)
+            {
+                if !terminals.is_empty() {
+                    self.precedences.declare(associativity, &terminals);
+                }
+            }
+        }
+    }
+
+    fn value_type_conflict(&self, head: &Symbol, value_type: &ValueType) -> Option<String> {
+        let head_id = self.symbols.reverse_lookup(head)?;
+        let declared = self.value_types.get(&head_id)?;
+        (declared != value_type).then(|| {
+            format!(
+                "{} is declared with value type {} and {}",
+                head.to_string(),
+                declared,
+                value_type
+            )
+        })
+    }
+}
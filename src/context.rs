@@ -0,0 +1,232 @@
+use std::ops::Range;
+
+use logos::Span;
+
+use crate::{
+    error::ParseError, production::ProductionId, source::TokenSource, state::StateId,
+    token::ProductionTokens,
+};
+
+///Everything a semantic action can reach while its production is reduced.
+///New runtime facts are added here as methods so the action signature
+///doesn't have to change.
+pub struct ActionContext<'a, AST, Token, TranslatorStack> {
+    ast: &'a mut AST,
+    tokens: ProductionTokens<'a, Token>,
+    translator_stack: &'a mut Vec<TranslatorStack>,
+    errors: &'a mut Vec<ParseError>,
+    production: ProductionId,
+    state: StateId,
+    lexer: LexerControl<'a, Token>,
+}
+
+///Token source an action can reach: its extras and lexing the lookahead
+///again. None in push sessions, which have no source.
+pub struct LexerControl<'a, Token> {
+    source: Option<&'a mut dyn TokenSource<Token>>,
+    //start of the lookahead
+    lookahead: usize,
+    //the extras were handed out, the tokens read past the lookahead may
+    //have to be lexed again
+    extras_reached: &'a mut bool,
+    //the lookahead was lexed again, the parser has to read it
+    relexed: &'a mut bool,
+}
+
+impl<'a, Token> LexerControl<'a, Token> {
+    pub fn new(
+        source: Option<&'a mut dyn TokenSource<Token>>,
+        lookahead: usize,
+        extras_reached: &'a mut bool,
+        relexed: &'a mut bool,
+    ) -> Self {
+        LexerControl {
+            source,
+            lookahead,
+            extras_reached,
+            relexed,
+        }
+    }
+
+    fn reborrow(&mut self) -> LexerControl<'_, Token> {
+        LexerControl {
+            source: match &mut self.source {
+                Some(source) => Some(&mut **source),
+                None => None,
+            },
+            lookahead: self.lookahead,
+            extras_reached: &mut *self.extras_reached,
+            relexed: &mut *self.relexed,
+        }
+    }
+}
+
+impl<'a, AST, Token, TranslatorStack> ActionContext<'a, AST, Token, TranslatorStack> {
+    pub fn new(
+        ast: &'a mut AST,
+        tokens: ProductionTokens<'a, Token>,
+        translator_stack: &'a mut Vec<TranslatorStack>,
+        errors: &'a mut Vec<ParseError>,
+        production: ProductionId,
+        state: StateId,
+        lexer: LexerControl<'a, Token>,
+    ) -> Self {
+        ActionContext {
+            ast,
+            tokens,
+            translator_stack,
+            errors,
+            production,
+            state,
+            lexer,
+        }
+    }
+
+    pub fn ast(&mut self) -> &mut AST {
+        self.ast
+    }
+
+    ///tokens shifted directly by the production body
+    pub fn tokens(&self) -> &ProductionTokens<'a, Token> {
+        &self.tokens
+    }
+
+    ///span of every body symbol, like @1..@n in yacc
+    pub fn spans(&self) -> &[Span] {
+        self.tokens.spans
+    }
+
+    ///span of the whole production, like @$ in yacc
+    pub fn span(&self) -> Span {
+        self.tokens.span()
+    }
+
+    pub fn translator_stack(&mut self) -> &mut Vec<TranslatorStack> {
+        self.translator_stack
+    }
+
+    pub fn errors(&mut self) -> &mut Vec<ParseError> {
+        self.errors
+    }
+
+    ///reports an error spanning the whole production
+    pub fn error(&mut self, message: String) {
+        let span = self.span();
+        self.errors.push(ParseError::new(span, message));
+    }
+
+    ///production being reduced
+    pub fn production(&self) -> ProductionId {
+        self.production
+    }
+
+    ///state of the automaton in which the production is reduced
+    pub fn state(&self) -> StateId {
+        self.state
+    }
+
+    ///`Lexer::extras` of the lexer being parsed, ex: to register a type name
+    ///the lexer has to tell apart from identifiers. None when the source has
+    ///no extras of type `E`, always in push sessions. Changes apply to the
+    ///tokens lexed after the lookahead, see relex_lookahead.
+    ///
+    ///Switching the lexer to another token type with `Lexer::morph` is out of
+    ///scope: the parser reads a single token type. Lexer modes are kept in
+    ///the extras instead and read by the lexer callbacks.
+    pub fn extras<E: 'static>(&mut self) -> Option<&mut E> {
+        let extras = self.lexer.source.as_mut()?.extras()?.downcast_mut::<E>()?;
+        *self.lexer.extras_reached = true;
+        Some(extras)
+    }
+
+    ///The parser has already read the token following the production when
+    ///the action runs. Lexes it again from its start with the extras as they
+    ///are now, the parser reads the new token once the action returns.
+    ///False when the source can't relex, always in push sessions.
+    pub fn relex_lookahead(&mut self) -> bool {
+        let lookahead = self.lexer.lookahead;
+        let relexed = self
+            .lexer
+            .source
+            .as_mut()
+            .is_some_and(|source| source.relex(lookahead));
+        *self.lexer.relexed |= relexed;
+        relexed
+    }
+
+    //context of the symbols an inline nonterminal was substituted by, as if
+    //its own production was reduced
+    pub(crate) fn inner_context<R>(
+        &mut self,
+        symbols: Range<usize>,
+        tokens: Range<usize>,
+        f: impl FnOnce(&mut ActionContext<AST, Token, TranslatorStack>) -> R,
+    ) -> R {
+        let all_spans = self.tokens.spans;
+        let all_tokens = self.tokens.tokens;
+        let span = self.inlined_span(&symbols);
+        let mut context = ActionContext {
+            ast: &mut *self.ast,
+            tokens: ProductionTokens {
+                tokens: &all_tokens[tokens],
+                spans: &all_spans[symbols],
+                span,
+            },
+            translator_stack: &mut *self.translator_stack,
+            errors: &mut *self.errors,
+            production: self.production,
+            state: self.state,
+            lexer: self.lexer.reborrow(),
+        };
+        f(&mut context)
+    }
+
+    //context of the production using an inline nonterminal, the substituted
+    //symbols are seen as the single inline symbol again
+    pub(crate) fn outer_context<R>(
+        &mut self,
+        symbols: Range<usize>,
+        tokens: Range<usize>,
+        f: impl FnOnce(&mut ActionContext<AST, Token, TranslatorStack>) -> R,
+    ) -> R
+    where
+        Token: Clone,
+    {
+        let mut outer_tokens = self.tokens.tokens.to_vec();
+        outer_tokens.drain(tokens);
+        let mut outer_spans = self.tokens.spans.to_vec();
+        let span = self.inlined_span(&symbols);
+        outer_spans.splice(symbols, [span]);
+        let mut context = ActionContext {
+            ast: &mut *self.ast,
+            tokens: ProductionTokens {
+                tokens: &outer_tokens,
+                spans: &outer_spans,
+                span: self.tokens.span(),
+            },
+            translator_stack: &mut *self.translator_stack,
+            errors: &mut *self.errors,
+            production: self.production,
+            state: self.state,
+            lexer: self.lexer.reborrow(),
+        };
+        f(&mut context)
+    }
+
+    //an empty substitution is located at the end of the previous symbol
+    fn inlined_span(&self, symbols: &Range<usize>) -> Span {
+        let spans = &self.tokens.spans[symbols.clone()];
+        match (spans.first(), spans.last()) {
+            (Some(first), Some(last)) => first.start..last.end,
+            _ => {
+                let end = symbols
+                    .start
+                    .checked_sub(1)
+                    .map_or(self.tokens.span().start, |previous| {
+                        self.tokens.spans[previous].end
+                    });
+                end..end
+            }
+        }
+    }
+}
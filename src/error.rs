@@ -1,19 +1,192 @@
-use std::ops::Range;
+use std::{error::Error, fmt::Display, ops::Range};
+
+use crate::{item::Item, state::StateId};
+
+///What went wrong, the message of a ParseError is rendered from it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    //the lexer failed to match the input or produced its error token
+    Lexical,
+    //the lookahead has no action in the current state
+    Syntax,
+    //the input ended before the start rule was complete
+    UnexpectedEof,
+    //reported by a semantic action
+    Semantic,
+}
 
 #[derive(Debug, Clone)]
 pub struct ParseError {
+    pub kind: ParseErrorKind,
     pub span: Range<usize>,
-    pub message: String,
+    //display name and terminal id of the unexpected token, the quoted text
+    //that failed to lex for lexical errors, None for errors that are not about a token
+    pub found: Option<String>,
+    pub terminal_id: Option<usize>,
+    //display names of the terminals the parser had an action on
+    pub expected: Vec<String>,
+    //state the error was detected in and its items that shifted the last symbol,
+    //they tell which productions the error interrupted
+    pub state: Option<StateId>,
+    pub items: Vec<Item>,
+    //message given by the grammar or by a semantic action, it replaces the
+    //rendered one
+    pub custom_message: Option<String>,
     //if the error is at the end of production set true
     pub production_end: bool,
+    //smallest edit of the input that lets parsing continue, empty if none
+    //was found
+    pub repair: Vec<Repair>,
+    //found too soon after a recovery, the error is likely caused by it. Like
+    //yacc, a compiler can leave these out.
+    pub cascaded: bool,
 }
 
 impl ParseError {
+    ///Semantic error with the given message
     pub fn new(span: Range<usize>, message: String) -> ParseError {
         ParseError {
+            kind: ParseErrorKind::Semantic,
             span,
-            message,
+            found: None,
+            terminal_id: None,
+            expected: vec![],
+            state: None,
+            items: vec![],
+            custom_message: Some(message),
             production_end: false,
+            repair: vec![],
+            cascaded: false,
+        }
+    }
+
+    ///Renders the error, the custom message when there is one
+    pub fn message(&self) -> String {
+        if let Some(message) = &self.custom_message {
+            return message.clone();
+        }
+        match self.kind {
+            ParseErrorKind::Lexical => match &self.found {
+                Some(found) => format!("Invalid token {found}"),
+                None => String::from("Invalid token"),
+            },
+            ParseErrorKind::Syntax | ParseErrorKind::UnexpectedEof => {
+                String::from("Expected ") + join_either_or(&self.expected).as_str()
+            }
+            ParseErrorKind::Semantic => String::from("Semantic error"),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message())
+    }
+}
+
+impl Error for ParseError {}
+
+fn join_either_or(items: &[String]) -> String {
+    match items.len() {
+        0 => "".to_string(),
+        1 => items[0].clone(),
+        2 => format!("{} or {}", items[0], items[1]),
+        _ => {
+            let all_but_last = &items[..items.len() - 1];
+            let last = &items[items.len() - 1];
+            format!("{} or {}", all_but_last.join(", "), last)
+        }
+    }
+}
+
+///Edit of the token stream suggested to fix a syntax error, terminals are
+///given by their display name. The parser applies them to its input only
+///when LR1_Parser::repair_input is set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Repair {
+    Insert {
+        terminal: String,
+        at: usize,
+    },
+    Delete {
+        span: Range<usize>,
+    },
+    Replace {
+        span: Range<usize>,
+        terminal: String,
+    },
+}
+
+impl Display for Repair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Repair::Insert { terminal, at } => write!(f, "insert `{terminal}` at {at}..{at}"),
+            Repair::Delete { span } => write!(f, "delete {}..{}", span.start, span.end),
+            Repair::Replace { span, terminal } => {
+                write!(f, "replace {}..{} with `{terminal}`", span.start, span.end)
+            }
         }
     }
 }
+
+///Error found while building a parser from a grammar.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GrammarError {
+    //terminals of the grammar that no TokenKind variant is named after,
+    //they could never be shifted
+    UnknownTerminals(Vec<String>),
+    //GrammarBuilder errors
+    MissingStartRule,
+    UndefinedNonterminals(Vec<String>),
+    UndeclaredTerminals(Vec<String>),
+    TerminalRules(Vec<String>),
+    InvalidBuilderCall(String),
+    //grammar composition errors
+    ImportConflicts(Vec<String>),
+    InvalidInline(String),
+    //templates used without a definition or with the wrong number of
+    //arguments, or defined twice with different parameters
+    InvalidTemplate(String),
+    //typed actions taking a value of another type than their body symbol
+    //produces, and productions of typed nonterminals without a typed action
+    MismatchedValueTypes(Vec<String>),
+}
+
+impl Display for GrammarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrammarError::UnknownTerminals(terminals) => write!(
+                f,
+                "grammar terminals {} do not match any token variant",
+                terminals.join(", ")
+            ),
+            GrammarError::MissingStartRule => write!(f, "grammar has no Start rule"),
+            GrammarError::UndefinedNonterminals(nonterminals) => write!(
+                f,
+                "nonterminals {} are used but have no rule",
+                nonterminals.join(", ")
+            ),
+            GrammarError::UndeclaredTerminals(terminals) => write!(
+                f,
+                "terminals {} are given a precedence but are not declared",
+                terminals.join(", ")
+            ),
+            GrammarError::TerminalRules(terminals) => write!(
+                f,
+                "terminals {} can not be the head of a rule",
+                terminals.join(", ")
+            ),
+            GrammarError::InvalidBuilderCall(message) => f.write_str(message),
+            GrammarError::ImportConflicts(conflicts) => {
+                write!(f, "grammars conflict: {}", conflicts.join(", "))
+            }
+            GrammarError::InvalidInline(message) => f.write_str(message),
+            GrammarError::InvalidTemplate(message) => f.write_str(message),
+            GrammarError::MismatchedValueTypes(mismatches) => {
+                write!(f, "typed actions do not match: {}", mismatches.join(", "))
+            }
+        }
+    }
+}
+
+impl Error for GrammarError {}
@@ -12,6 +12,7 @@ pub fn compute_first_set<AST, Token: TokenKind, TranslatorStack>(
     let mut first_map: IndexMap<SymbolId, IndexSet<SymbolId>> = IndexMap::new();
     let mut first_map_: IndexMap<SymbolId, IndexSet<SymbolId>> = IndexMap::new();
     let mut productions_hashmap: IndexMap<SymbolId, Vec<Vec<SymbolId>>> = IndexMap::new();
+    let nullable = compute_nullable_set(grammar);
     let mut symbols = vec![EOF_SYMBOL_ID];
     symbols.extend(grammar.symbols.terminals.clone());
     symbols.extend(grammar.symbols.non_terminals.clone());
@@ -41,18 +42,10 @@ pub fn compute_first_set<AST, Token: TokenKind, TranslatorStack>(
                 x if grammar.symbols.non_terminal(x) => {
                     let p = productions_hashmap.get(symbol).unwrap();
                     p.iter().for_each(|body| {
-                        if !body.is_empty() {
-                            let first = body.first().unwrap();
-                            let set_ = match first {
-                                x if grammar.symbols.terminal(x) => IndexSet::from([*x]),
-                                x if grammar.symbols.non_terminal(x) => {
-                                    first_map.get(first).unwrap().iter().cloned().collect()
-                                }
-                                _ => return,
-                            };
-                            let set = first_map.get_mut(symbol).unwrap();
-                            set.extend(set_);
-                        }
+                        //FIRST of the body goes on past nullable symbols
+                        let (set_, _) = first_of_sequence(&first_map, &nullable, body);
+                        let set = first_map.get_mut(symbol).unwrap();
+                        set.extend(set_);
                     });
                 }
                 _ => {}
@@ -61,3 +54,44 @@ pub fn compute_first_set<AST, Token: TokenKind, TranslatorStack>(
     }
     first_map
 }
+
+///Nonterminals deriving the empty string, ex: the heads of mid-rule actions
+pub fn compute_nullable_set<AST, Token: TokenKind, TranslatorStack>(
+    grammar: &Grammar<AST, Token, TranslatorStack>,
+) -> IndexSet<SymbolId> {
+    let mut nullable: IndexSet<SymbolId> = IndexSet::new();
+    loop {
+        let count = nullable.len();
+        for production in grammar.productions.vec.iter() {
+            if production
+                .body
+                .iter()
+                .all(|symbol| nullable.contains(symbol))
+            {
+                nullable.insert(production.head);
+            }
+        }
+        if count == nullable.len() {
+            break;
+        }
+    }
+    nullable
+}
+
+///FIRST of a sequence of symbols, and whether the whole sequence is nullable
+pub fn first_of_sequence(
+    first_set: &IndexMap<SymbolId, IndexSet<SymbolId>>,
+    nullable: &IndexSet<SymbolId>,
+    symbols: &[SymbolId],
+) -> (IndexSet<SymbolId>, bool) {
+    let mut first = IndexSet::new();
+    for symbol in symbols.iter() {
+        if let Some(symbol_first) = first_set.get(symbol) {
+            first.extend(symbol_first.iter().copied());
+        }
+        if !nullable.contains(symbol) {
+            return (first, false);
+        }
+    }
+    (first, true)
+}
@@ -1,7 +1,7 @@
 use indexmap::{IndexMap, IndexSet};
 
 use crate::{
-    first::compute_first_set,
+    first::{compute_first_set, compute_nullable_set, first_of_sequence},
     grammar::Grammar,
     production::Production,
     symbol::{SymbolId, AUGMENT_START_SYMBOL_ID, EOF_SYMBOL_ID},
@@ -40,36 +40,10 @@ pub fn compute_follow_set<AST, Token: TokenKind, TranslatorStack>(
     };
 
     let first = compute_first_set(grammar);
+    let nullable = compute_nullable_set(grammar);
 
-    //A -> a B D , then everything in First(D) is in Follow(B)
-    //this loop implements above def
-    for non_terminal in grammar.symbols.non_terminals.iter() {
-        for production in grammar.productions.vec.iter() {
-            let with_indexes: Vec<(usize, SymbolId)> = production
-                .body
-                .iter()
-                .enumerate()
-                .filter(|(_, symbol)| symbol.eq(&non_terminal))
-                .map(|(index, symbol)| (index, *symbol))
-                .collect();
-
-            //not so good logic but hope it works
-            for (index, _) in with_indexes.iter() {
-                if index.clone() == production.body.len() - 1 {
-                    continue;
-                } else {
-                    let next_ = production.body.get(index + 1).unwrap();
-                    let first_ = first.get(next_).unwrap();
-                    follow_map
-                        .entry(non_terminal.clone())
-                        .and_modify(|token_types| token_types.extend(first_.clone()))
-                        .or_insert(IndexSet::from_iter(first_.iter().cloned()));
-                }
-            }
-        }
-    }
-
-    //A->aB then everything in  Follow(A) is in Follow(B)
+    //A -> a B D , then everything in First(D) is in Follow(B), and if D is
+    //nullable everything in Follow(A) is in Follow(B)
     loop {
         let follow_count_func = |follow_map: &IndexMap<SymbolId, IndexSet<SymbolId>>| {
             follow_map
@@ -82,15 +56,19 @@ pub fn compute_follow_set<AST, Token: TokenKind, TranslatorStack>(
         for production in grammar.productions.vec.iter() {
             if production.head.eq(&AUGMENT_START_SYMBOL_ID) {
                 continue;
-            } else {
-                let last_symbol = production.body.last().unwrap();
-                let follow_head = match follow_map.get(&production.head) {
-                    Some(set) => set.clone(),
-                    None => continue,
-                };
-                follow_map
-                    .entry(last_symbol.clone())
-                    .and_modify(|token_types| token_types.extend(follow_head));
+            }
+            for (index, symbol) in production.body.iter().enumerate() {
+                if !grammar.symbols.non_terminal(symbol) {
+                    continue;
+                }
+                let (mut follow, rest_nullable) =
+                    first_of_sequence(&first, &nullable, &production.body[index + 1..]);
+                if rest_nullable {
+                    if let Some(follow_head) = follow_map.get(&production.head) {
+                        follow.extend(follow_head.iter().copied());
+                    }
+                }
+                follow_map.entry(*symbol).or_default().extend(follow);
             }
         }
         if follow_map_count_before == follow_count_func(&follow_map) {
@@ -1,13 +1,22 @@
 //cfg grammar should be in bnf format
 
-use std::{fmt::Debug, hash::Hash};
+use std::{
+    any::{type_name, TypeId},
+    fmt::Debug,
+    hash::Hash,
+};
 
 use indexmap::{IndexMap, IndexSet};
 
 use crate::{
-    production::{ProductionId, Productions},
+    error::GrammarError,
+    interner::Interner,
+    precedence::Precedences,
+    production::{Production, ProductionId, Productions},
     symbol::{SymbolId, Symbols, START_SYMBOL_ID},
+    template::Template,
     token::TokenKind,
+    value::{SemanticValue, ValueType},
 };
 
 #[derive(Debug, Clone)]
@@ -17,6 +26,20 @@ pub struct Grammar<AST, Token, TranslatorStack> {
     pub productions: Productions<AST, Token, TranslatorStack>,
     //used only when constructing table, no need for parsing
     pub production_head_map: IndexMap<SymbolId, IndexSet<ProductionId>>,
+    //value type declared by the typed actions of each nonterminal
+    pub value_types: IndexMap<SymbolId, ValueType>,
+    //used only when constructing table, no need for parsing
+    pub precedences: Precedences,
+    //parameterised nonterminals, instantiated into productions when used
+    pub templates: IndexMap<String, Template<AST, Token, TranslatorStack>>,
+    //nonterminals substituted into their callers before the automaton is built
+    pub inline: IndexSet<SymbolId>,
+    //nonterminals generated for mid-rule actions, with the number of symbols
+    //and of terminals to their left
+    pub mid_rules: IndexMap<SymbolId, (usize, usize)>,
+    //templates misused by the grammar! macro, which can't return them,
+    //reported by LR1_Parser::try_new
+    pub errors: Vec<GrammarError>,
 }
 
 impl<AST, Token, TranslatorStack> Hash for Grammar<AST, Token, TranslatorStack>
@@ -27,6 +50,9 @@ where
         self.symbols.hash(state);
         self.start.hash(state);
         self.productions.hash(state);
+        self.precedences.hash(state);
+        self.inline.iter().for_each(|symbol| symbol.hash(state));
+        self.mid_rules.iter().for_each(|entry| entry.hash(state));
     }
 }
 
@@ -42,8 +68,100 @@ where
             start: START_SYMBOL_ID,
             productions: Productions::new(),
             production_head_map: IndexMap::new(),
+            value_types: IndexMap::new(),
+            precedences: Precedences::default(),
+            templates: IndexMap::new(),
+            inline: IndexSet::new(),
+            mid_rules: IndexMap::new(),
+            errors: vec![],
+        }
+    }
+
+    ///Records the semantic value type of a nonterminal, the type of its first
+    ///typed action. Every typed action of the same nonterminal has to return
+    ///the same type, check_value_types reports the others.
+    pub fn declare_value_type(&mut self, head: SymbolId, value_type: ValueType) {
+        self.value_types.entry(head).or_insert(value_type);
+    }
+
+    ///Checks the typed actions against the value types of the symbols, so a
+    ///mismatch is reported before parsing rather than panicking in the middle
+    ///of it. Every argument of a typed action has the value type of its body
+    ///symbol: the token type for terminals, `()` for nonterminals without
+    ///typed actions, or `SemanticValue` for any. Every production of a
+    ///nonterminal with a value type has a typed action, returning it.
+    pub fn check_value_types(&self) -> Result<(), GrammarError>
+    where
+        Token: 'static,
+    {
+        let unit = ValueType::of::<()>("()");
+        let mut mismatches = vec![];
+        for production in self.productions.vec.iter() {
+            let declared = self.value_types.get(&production.head);
+            if let (Some(declared), Some(value_type)) = (declared, &production.value_type) {
+                if declared != value_type {
+                    mismatches.push(format!(
+                        "{} is declared with value type {declared} and {value_type}",
+                        self.symbols.lookup(production.head).to_string(),
+                    ));
+                }
+            }
+            let Some(argument_types) = &production.argument_types else {
+                if production.value_action.is_none() {
+                    if let Some(value_type) = self.value_types.get(&production.head) {
+                        mismatches.push(format!(
+                            "{} has no typed action but {} has value type {value_type}",
+                            self.production_name(production),
+                            self.symbols.lookup(production.head).to_string(),
+                        ));
+                    }
+                }
+                continue;
+            };
+            for (position, (symbol, argument_type)) in
+                production.body.iter().zip(argument_types).enumerate()
+            {
+                //SemanticValue takes any value untyped
+                if argument_type.id == TypeId::of::<SemanticValue>() {
+                    continue;
+                }
+                let (value_type, matches) = if self.symbols.terminal(symbol) {
+                    //the token type is named without its path
+                    let value_type = type_name::<Token>().rsplit("::").next().unwrap();
+                    (value_type, argument_type.id == TypeId::of::<Token>())
+                } else {
+                    let value_type = self.value_types.get(symbol).unwrap_or(&unit);
+                    (value_type.name.as_str(), argument_type == value_type)
+                };
+                if !matches {
+                    mismatches.push(format!(
+                        "argument {} of the typed action of {} is {argument_type} but {} has value type {value_type}",
+                        position + 1,
+                        self.production_name(production),
+                        self.symbols.lookup(*symbol).to_string(),
+                    ));
+                }
+            }
+        }
+        match mismatches.is_empty() {
+            true => Ok(()),
+            false => Err(GrammarError::MismatchedValueTypes(mismatches)),
         }
     }
+
+    //`Head -> Body` as written in the grammar
+    fn production_name(&self, production: &Production<AST, Token, TranslatorStack>) -> String {
+        let body: Vec<String> = production
+            .body
+            .iter()
+            .map(|symbol| self.symbols.lookup(*symbol).to_string())
+            .collect();
+        format!(
+            "{} -> {}",
+            self.symbols.lookup(production.head).to_string(),
+            body.join(" ")
+        )
+    }
 }
 
 //need to improve lot of repetetion
@@ -52,27 +170,49 @@ where
 macro_rules! start_production {
     (
         $grammar:ident,
-        $($start_non_terminal:ident)+
-        $({ |$s_arg1:ident,$s_arg2:ident,$s_arg3:ident,$s_arg4:ident| $s_expr:expr})?
+        $(? { |$p_ctx:ident| $p_expr:expr})?
+        $($start_non_terminal:ident $(<$($arg:ident),+>)? $({ |$m_ctx:ident| $m_expr:expr})?)+
+        $(! $message:literal $({ |$s_ctx:ident| $s_expr:expr})?)?
+        $(=> |$($v_arg:ident : $v_ty:ty),*| -> $v_ret:ty $v_body:block)?
     ) => {
-            let mut body_ : Vec<SymbolId> = Vec::new();
-            $(
-                let non_terminal = Symbol::NONTERMINAL(stringify!($start_non_terminal).to_string());
-                let non_terminal_id = $grammar.symbols.intern(non_terminal);
-                body_.push(non_terminal_id);
-            )+
+            let head_id = SymbolId(2);
+            $crate::production_body!(
+                $grammar, head_id, body_, last_action_,
+                $($start_non_terminal $(<$($arg),+>)? $({ |$m_ctx| $m_expr})?)+
+            );
             #[allow(unused_mut)]
             let mut production = Production {
-                head: SymbolId(2),
+                head: head_id,
                 body: body_,
                 error_message: None,
                 action:None,
-                action_tokens : quote::quote!{Some(Rc::new(|ast,token_stack,tl_stack,errors| {}))},
+                action_tokens : quote::quote!{None}.into(),
+                value_action: None,
+                value_action_tokens: quote::quote!{None}.into(),
+                argument_types: None,
+                value_type: None,
+                predicate: None,
+                predicate_tokens: quote::quote!{None}.into(),
                 index: $grammar.productions.vec.len()
             };
+            if let Some((action, action_tokens)) = last_action_ {
+                production.action = Some(action);
+                production.action_tokens = action_tokens.into();
+            }
             $(
-                production.action = Some(Rc::new(|$s_arg1,$s_arg2,$s_arg3,$s_arg4| $s_expr));
-                production.action_tokens = quote::quote!{Some(Rc::new(|$s_arg1,$s_arg2,$s_arg3,$s_arg4| $s_expr))};
+                production.error_message = Some(String::from($message));
+                $(
+                    assert!(production.action.is_none(), "Start has two actions after its last symbol");
+                    production.action = Some($crate::production::semantic_action(|$s_ctx| $s_expr));
+                    production.action_tokens = quote::quote!{Some(semantic_action(|$s_ctx| $s_expr))}.into();
+                )?
+            )?
+            $(
+                $crate::value_action!($grammar, production, Start, |$($v_arg : $v_ty),*| -> $v_ret $v_body);
+            )?
+            $(
+                production.predicate = Some($crate::predicate::semantic_predicate(|$p_ctx| $p_expr));
+                production.predicate_tokens = quote::quote!{Some(semantic_predicate(|$p_ctx| $p_expr))}.into();
             )?
             $grammar.productions.intern(production);
     }
@@ -83,8 +223,11 @@ macro_rules! terminal_production {
     (
         $grammar:ident,
         $head:ident,
+        $(? { |$p_ctx:ident| $p_expr:expr})?
         [$terminal:expr]
-        $({ |$s_arg1:ident,$s_arg2:ident,$s_arg3:ident,$s_arg4:ident| $s_expr:expr})?
+        $(! $message:literal)?
+        $({ |$s_ctx:ident| $s_expr:expr})?
+        $(=> |$($v_arg:ident : $v_ty:ty),*| -> $v_ret:ty $v_body:block)?
     ) => {
 
         //lhs
@@ -93,7 +236,7 @@ macro_rules! terminal_production {
 
         //rhs
         let mut body_ : Vec<SymbolId> = Vec::new();
-        let terminal = Symbol::TERMINAL($terminal.to_string());
+        let terminal = Symbol::TERMINAL($crate::token::TerminalName::terminal_name(&$terminal));
         let terminal_id = $grammar.symbols.intern(terminal);
         body_.push(terminal_id);
 
@@ -104,13 +247,32 @@ macro_rules! terminal_production {
             body: body_,
             error_message: None,
             action:None,
-            action_tokens : quote::quote!{Some(Rc::new(|ast,token_stack,tl_stack,errors| {}))},
+            action_tokens : quote::quote!{None}.into(),
+            value_action: None,
+            value_action_tokens: quote::quote!{None}.into(),
+            argument_types: None,
+            value_type: None,
+            predicate: None,
+            predicate_tokens: quote::quote!{None}.into(),
             index: $grammar.productions.vec.len()
         };
 
         $(
-            production.action = Some(Rc::new(|$s_arg1,$s_arg2,$s_arg3,$s_arg4| $s_expr));
-            production.action_tokens = quote::quote!{Some(Rc::new(|$s_arg1,$s_arg2,$s_arg3,$s_arg4| $s_expr))};
+            production.error_message = Some(String::from($message));
+        )?
+
+        $(
+            production.action = Some($crate::production::semantic_action(|$s_ctx| $s_expr));
+            production.action_tokens = quote::quote!{Some(semantic_action(|$s_ctx| $s_expr))}.into();
+        )?
+
+        $(
+            $crate::value_action!($grammar, production, $head, |$($v_arg : $v_ty),*| -> $v_ret $v_body);
+        )?
+
+        $(
+            production.predicate = Some($crate::predicate::semantic_predicate(|$p_ctx| $p_expr));
+            production.predicate_tokens = quote::quote!{Some(semantic_predicate(|$p_ctx| $p_expr))}.into();
         )?
 
         $grammar.productions.intern(production);
@@ -122,8 +284,10 @@ macro_rules! non_terminal_production {
     (
         $grammar:ident,
         $head:ident,
-        $($non_terminal:ident)+
-        $({ |$s_arg1:ident,$s_arg2:ident,$s_arg3:ident,$s_arg4:ident| $s_expr:expr})?
+        $(? { |$p_ctx:ident| $p_expr:expr})?
+        $($non_terminal:ident $(<$($arg:ident),+>)? $({ |$m_ctx:ident| $m_expr:expr})?)+
+        $(! $message:literal $({ |$s_ctx:ident| $s_expr:expr})?)?
+        $(=> |$($v_arg:ident : $v_ty:ty),*| -> $v_ret:ty $v_body:block)?
     ) => {
 
         //lhs
@@ -131,12 +295,10 @@ macro_rules! non_terminal_production {
         let head_id = $grammar.symbols.intern(head);
 
         //rhs
-        let mut body_ : Vec<SymbolId> = Vec::new();
-        $(
-            let non_terminal = Symbol::NONTERMINAL(stringify!($non_terminal).to_string());
-            let non_terminal_id = $grammar.symbols.intern(non_terminal);
-            body_.push(non_terminal_id);
-        )+
+        $crate::production_body!(
+            $grammar, head_id, body_, last_action_,
+            $($non_terminal $(<$($arg),+>)? $({ |$m_ctx| $m_expr})?)+
+        );
 
         //production
         #[allow(unused_mut)]
@@ -145,66 +307,389 @@ macro_rules! non_terminal_production {
             body: body_,
             error_message: None,
             action:None,
-            action_tokens : quote::quote!{Some(Rc::new(|ast,token_stack,tl_stack,errors| {}))},
+            action_tokens : quote::quote!{None}.into(),
+            value_action: None,
+            value_action_tokens: quote::quote!{None}.into(),
+            argument_types: None,
+            value_type: None,
+            predicate: None,
+            predicate_tokens: quote::quote!{None}.into(),
             index: $grammar.productions.vec.len()
         };
 
+        if let Some((action, action_tokens)) = last_action_ {
+            production.action = Some(action);
+            production.action_tokens = action_tokens.into();
+        }
+
+        $(
+            production.error_message = Some(String::from($message));
+            $(
+                assert!(
+                    production.action.is_none(),
+                    "{} has two actions after its last symbol",
+                    stringify!($head)
+                );
+                production.action = Some($crate::production::semantic_action(|$s_ctx| $s_expr));
+                production.action_tokens = quote::quote!{Some(semantic_action(|$s_ctx| $s_expr))}.into();
+            )?
+        )?
+
         $(
-            production.action = Some(Rc::new(|$s_arg1,$s_arg2,$s_arg3,$s_arg4| $s_expr));
-            production.action_tokens = quote::quote!{Some(Rc::new(|$s_arg1,$s_arg2,$s_arg3,$s_arg4| $s_expr))};
+            $crate::value_action!($grammar, production, $head, |$($v_arg : $v_ty),*| -> $v_ret $v_body);
+        )?
+
+        $(
+            production.predicate = Some($crate::predicate::semantic_predicate(|$p_ctx| $p_expr));
+            production.predicate_tokens = quote::quote!{Some(semantic_predicate(|$p_ctx| $p_expr))}.into();
         )?
 
         $grammar.productions.intern(production);
     };
 }
 
+///Body of a production, `{ action }` blocks between its symbols are mid-rule
+///actions desugared into empty productions. Binds `$body` and `$last`, the
+///action written after the last symbol.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! production_body {
+    (
+        $grammar:ident,
+        $head_id:ident,
+        $body:ident,
+        $last:ident,
+        $($symbol:ident $(<$($arg:ident),+>)? $({ |$m_ctx:ident| $m_expr:expr})?)+
+    ) => {
+        #[allow(clippy::vec_init_then_push)]
+        let mut $body: Vec<SymbolId> = vec![];
+        #[allow(unused_mut)]
+        let mut mid_actions_ = vec![];
+        $(
+            $body.push($crate::body_symbol!($grammar, $symbol $(<$($arg),+>)?));
+            $(
+                mid_actions_.push((
+                    $body.len(),
+                    $crate::production::semantic_action(|$m_ctx| $m_expr),
+                    quote::quote!{Some(semantic_action(|$m_ctx| $m_expr))},
+                ));
+            )?
+        )+
+        let ($body, $last) = $grammar.mid_rule_actions($head_id, $body, mid_actions_);
+    };
+}
+
+///Defines one alternative of a template such as `Comma<T> -> T | Comma<T> CommaToken T`,
+///its actions are shared by every instantiation. A typed action sees each
+///parameter as `SemanticValue`, ex: `|t: T| -> Vec<T>` takes and returns boxed values.
+#[macro_export]
+macro_rules! template_production {
+    (
+        $grammar:ident,
+        $head:ident,
+        $params:tt,
+        $(? { |$p_ctx:ident| $p_expr:expr})?
+        $($symbol:ident $(<$($arg:ident),+>)? $({ |$m_ctx:ident| $m_expr:expr})?)+
+        $(! $message:literal $({ |$s_ctx:ident| $s_expr:expr})?)?
+        $(=> |$($v_arg:ident : $v_ty:ty),*| -> $v_ret:ty $v_body:block)?
+    ) => {
+        $crate::template_actions!($($symbol $(<$($arg),+>)? $({ |$m_ctx| $m_expr })?)+);
+        //an action after the last symbol is the action of the alternative
+        #[allow(unused_mut, unused_assignments)]
+        let mut last_action_ = None;
+        $(
+            $(
+                last_action_ = Some((
+                    $crate::production::semantic_action(|$m_ctx| $m_expr),
+                    $crate::production::SourceTokens::from(
+                        quote::quote!{Some(semantic_action(|$m_ctx| $m_expr))}
+                    ),
+                ));
+            )?
+        )+
+        #[allow(unused_mut)]
+        let mut alternative = $crate::template::TemplateAlternative {
+            body: vec![$(
+                (
+                    stringify!($symbol).to_string(),
+                    vec![$($(stringify!($arg).to_string()),+)?],
+                )
+            ),+],
+            error_message: None,
+            action: None,
+            action_tokens: quote::quote!{None}.into(),
+            value_action: None,
+            value_action_tokens: quote::quote!{None}.into(),
+            argument_types: None,
+            value_type: None,
+            predicate: None,
+            predicate_tokens: quote::quote!{None}.into(),
+        };
+        $(
+            alternative.predicate = Some($crate::predicate::semantic_predicate(|$p_ctx| $p_expr));
+            alternative.predicate_tokens = quote::quote!{Some(semantic_predicate(|$p_ctx| $p_expr))}.into();
+        )?
+        if let Some((action, action_tokens)) = last_action_ {
+            alternative.action = Some(action);
+            alternative.action_tokens = action_tokens;
+        }
+        $(
+            alternative.error_message = Some(String::from($message));
+            $(
+                alternative.action = Some($crate::production::semantic_action(|$s_ctx| $s_expr));
+                alternative.action_tokens = quote::quote!{Some(semantic_action(|$s_ctx| $s_expr))}.into();
+            )?
+        )?
+        $(
+            $crate::value_action!(
+                @action alternative, $head, $params,
+                |$($v_arg : $v_ty),*| -> $v_ret $v_body
+            );
+        )?
+        if let Err(error) =
+            $grammar.define_template(stringify!($head), $crate::template_params!($params), alternative)
+        {
+            $grammar.errors.push(error);
+        }
+    };
+}
+
+///Rejects an action followed by another symbol in a template alternative,
+///only the last symbol of a template alternative can have one
+#[doc(hidden)]
+#[macro_export]
+macro_rules! template_actions {
+    () => {};
+    ({ $($action:tt)* }) => {};
+    ({ $($action:tt)* } $($rest:tt)+) => {
+        compile_error!("mid-rule actions are not supported in templates");
+    };
+    ($token:tt $($rest:tt)*) => {
+        $crate::template_actions!($($rest)*);
+    };
+}
+
+///Names of the parameters of a template, `&["T", "U"]` for `[T, U]`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! template_params {
+    ([$($param:ident),+]) => {
+        &[$(stringify!($param)),+]
+    };
+}
+
+//grammar! hands every rule with its bracketed alternatives to both
+//template_rule! and non_terminal_rule!, each one only expands its own kind
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! template_rule {
+    ($grammar:ident, $head:ident <$($param:ident),+>, $($alternatives:tt)+) => {
+        $crate::template_rule!(@params $grammar, $head, [$($param),+], $($alternatives)+)
+    };
+    (@params $grammar:ident, $head:ident, $params:tt, $([$($alternative:tt)+])+) => {
+        $(
+            $crate::template_production!($grammar, $head, $params, $($alternative)+);
+        )+
+    };
+    ($grammar:ident, $head:ident, $($alternatives:tt)+) => {};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! non_terminal_rule {
+    ($grammar:ident, $head:ident <$($param:ident),+>, $($alternatives:tt)+) => {};
+    ($grammar:ident, $head:ident, $([$($alternative:tt)+])+) => {
+        $(
+            $crate::non_terminal_production!($grammar, $head, $($alternative)+);
+        )+
+    };
+}
+
+///`#[inline]` before a rule substitutes the nonterminal into the bodies using it
+#[doc(hidden)]
+#[macro_export]
+macro_rules! rule_attribute {
+    ($grammar:ident, inline, $head:ident) => {
+        $grammar.mark_inline(stringify!($head));
+    };
+    ($grammar:ident, $attribute:ident, $head:ident) => {
+        compile_error!(concat!(
+            "unknown rule attribute #[",
+            stringify!($attribute),
+            "]"
+        ));
+    };
+}
+
+///Symbol id of a body symbol, `Name<Arg>` instantiates the template `Name`
+#[doc(hidden)]
+#[macro_export]
+macro_rules! body_symbol {
+    ($grammar:ident, error) => {
+        $crate::symbol::ERROR_SYMBOL_ID
+    };
+    ($grammar:ident, $symbol:ident) => {
+        $grammar.symbols.intern(Symbol::NONTERMINAL(stringify!($symbol).to_string()))
+    };
+    ($grammar:ident, $symbol:ident <$($arg:ident),+>) => {
+        match $grammar.instantiate(stringify!($symbol), &[$(stringify!($arg)),+]) {
+            Ok(symbol) => symbol,
+            //any symbol does, try_new reports the error before using it
+            Err(error) => {
+                $grammar.errors.push(error);
+                $crate::symbol::ERROR_SYMBOL_ID
+            }
+        }
+    };
+}
+
+///Sets the typed action of a production, the closure receives the semantic
+///values of the body and returns the value of the head
+#[doc(hidden)]
+#[macro_export]
+macro_rules! value_action {
+    (
+        $grammar:ident,
+        $production:ident,
+        $head:ident,
+        |$($arg:ident : $ty:ty),*| -> $ret:ty $body:block
+    ) => {
+        $crate::value_action!(@action $production, $head, [], |$($arg : $ty),*| -> $ret $body);
+        $grammar.declare_value_type($production.head, $production.value_type.clone().unwrap());
+    };
+    //template parameters are type aliases of SemanticValue in the action, the
+    //values of their symbols are passed untyped
+    (
+        @action
+        $production:ident,
+        $head:ident,
+        [$($param:ident),*],
+        |$($arg:ident : $ty:ty),*| -> $ret:ty $body:block
+    ) => {
+        let arity: usize = 0 $(+ { let _ = stringify!($arg); 1 })*;
+        assert_eq!(
+            arity,
+            $production.body.len(),
+            "typed action of {} takes {} values but its body has {} symbols",
+            stringify!($head),
+            arity,
+            $production.body.len()
+        );
+        //the types are taken with the template parameters in scope
+        {
+            $(
+                #[allow(non_camel_case_types, dead_code)]
+                type $param = $crate::value::SemanticValue;
+            )*
+            $production.argument_types =
+                Some(vec![$($crate::value::ValueType::of::<$ty>(stringify!($ty))),*]);
+            $production.value_type = Some($crate::value::ValueType::of::<$ret>(stringify!($ret)));
+        }
+        $production.value_action = Some(std::sync::Arc::new(
+            |values: Vec<$crate::value::SemanticValue>| -> Result<$crate::value::SemanticValue, String> {
+                $(
+                    #[allow(non_camel_case_types, dead_code)]
+                    type $param = $crate::value::SemanticValue;
+                )*
+                #[allow(unused_mut)]
+                let mut values = values.into_iter();
+                $(let $arg: $ty = $crate::value::take_value(&mut values, stringify!($head))?;)*
+                let value: $ret = $body;
+                Ok(Box::new(value))
+            },
+        ));
+        $production.value_action_tokens = quote::quote!{
+            Some(Arc::new(|values: Vec<SemanticValue>| -> Result<SemanticValue, String> {
+                $(
+                    #[allow(non_camel_case_types, dead_code)]
+                    type $param = SemanticValue;
+                )*
+                #[allow(unused_mut)]
+                let mut values = values.into_iter();
+                $(let $arg: $ty = take_value(&mut values, stringify!($head))?;)*
+                let value: $ret = $body;
+                Ok(Box::new(value))
+            }))
+        }
+        .into();
+    };
+}
+
 #[macro_export]
 macro_rules! grammar{
     (
         Start -> $(
-            $($start_non_terminal:ident)+
-            $({|$s_arg1:ident,$s_arg2:ident,$s_arg3:ident,$s_arg4:ident| $s_expr:expr})?
+            $(?{|$sp_ctx:ident| $sp_expr:expr})?
+            $($start_non_terminal:ident $(<$($s_arg:ident),+>)? $({|$sm_ctx:ident| $sm_expr:expr})?)+
+            $(! $s_message:literal $({|$s_ctx:ident| $s_expr:expr})?)?
+            $(=> |$($sv_arg:ident : $sv_ty:ty),*| -> $sv_ret:ty $sv_body:block)?
         )|+;
 
         $([non_terminal_productions])?
 
         $(
-            $non_terminal_head:ident -> $(
-                $($non_terminal:ident)+
-                $({|$n_arg1:ident,$n_arg2:ident,$n_arg3:ident,$n_arg4:ident| $n_expr:expr})?
+            $(#[$n_attr:ident])?
+            $non_terminal_head:ident $(<$($param:ident),+>)? -> $(
+                $(?{|$np_ctx:ident| $np_expr:expr})?
+                $($non_terminal:ident $(<$($n_arg:ident),+>)? $({|$nm_ctx:ident| $nm_expr:expr})?)+
+                $(! $n_message:literal $({|$n_ctx:ident| $n_expr:expr})?)?
+                $(=> |$($nv_arg:ident : $nv_ty:ty),*| -> $nv_ret:ty $nv_body:block)?
             )|+
         ;)*
 
         [terminal_productions]
 
         $(
+            $(#[$e_attr:ident])?
             $terminal_head:ident -> $(
+                $(?{|$ep_ctx:ident| $ep_expr:expr})?
                 [$end_terminal:expr]
-                $({|$e_arg1:ident,$e_arg2:ident,$e_arg3:ident,$e_arg4:ident| $e_expr:expr})?
+                $(! $e_message:literal)?
+                $({|$e_ctx:ident| $e_expr:expr})?
+                $(=> |$($ev_arg:ident : $ev_ty:ty),*| -> $ev_ret:ty $ev_body:block)?
             )|+;
         )+
     ) => {{
         let mut grammar = Grammar::new();
+        //templates, defined first so any production can instantiate them
+        $(
+            $crate::template_rule!(
+                grammar,
+                $non_terminal_head $(<$($param),+>)?,
+                $([
+                    $(?{|$np_ctx| $np_expr})?
+                    $($non_terminal $(<$($n_arg),+>)? $({|$nm_ctx| $nm_expr})?)+
+                    $(! $n_message $({|$n_ctx| $n_expr})?)?
+                    $(=> |$($nv_arg : $nv_ty),*| -> $nv_ret $nv_body)?
+                ])+
+            );
+        )*
+
         //start production
         $(
             $crate::start_production!(
                 grammar,
-                $($start_non_terminal)+
-                $({ |$s_arg1,$s_arg2,$s_arg3,$s_arg4| $s_expr })?
+                $(?{|$sp_ctx| $sp_expr})?
+                $($start_non_terminal $(<$($s_arg),+>)? $({ |$sm_ctx| $sm_expr })?)+
+                $(! $s_message $({ |$s_ctx| $s_expr })?)?
+                $(=> |$($sv_arg : $sv_ty),*| -> $sv_ret $sv_body)?
             );
         )+
 
         //non_terminal_productions
         $(
-            $(
-                $crate::non_terminal_production!(
-                    grammar,
-                    $non_terminal_head,
-                    $($non_terminal)+
-                    $({|$n_arg1,$n_arg2,$n_arg3,$n_arg4| $n_expr})?
-                );
-            )+
-        )?
+            $crate::non_terminal_rule!(
+                grammar,
+                $non_terminal_head $(<$($param),+>)?,
+                $([
+                    $(?{|$np_ctx| $np_expr})?
+                    $($non_terminal $(<$($n_arg),+>)? $({|$nm_ctx| $nm_expr})?)+
+                    $(! $n_message $({|$n_ctx| $n_expr})?)?
+                    $(=> |$($nv_arg : $nv_ty),*| -> $nv_ret $nv_body)?
+                ])+
+            );
+        )*
 
         //terminal_productions
         $(
@@ -212,12 +697,27 @@ macro_rules! grammar{
                 $crate::terminal_production!(
                     grammar,
                     $terminal_head,
+                    $(?{|$ep_ctx| $ep_expr})?
                     [$end_terminal]
-                    $({|$e_arg1,$e_arg2,$e_arg3,$e_arg4| $e_expr})?
+                    $(! $e_message)?
+                    $({|$e_ctx| $e_expr})?
+                    $(=> |$($ev_arg : $ev_ty),*| -> $ev_ret $ev_body)?
                 );
             )+
         )+
 
+        //rule attributes
+        $(
+            $(
+                $crate::rule_attribute!(grammar, $n_attr, $non_terminal_head);
+            )?
+        )*
+        $(
+            $(
+                $crate::rule_attribute!(grammar, $e_attr, $terminal_head);
+            )?
+        )+
+
         grammar
     }}
 }
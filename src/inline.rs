@@ -0,0 +1,271 @@
+use std::{ops::Range, sync::Arc};
+
+use indexmap::{IndexMap, IndexSet};
+use quote::quote;
+
+use crate::{
+    error::GrammarError,
+    grammar::Grammar,
+    interner::Interner,
+    production::{Production, ProductionId, Productions, SemanticAction, ValueAction},
+    symbol::{Symbol, SymbolId, START_SYMBOL_ID},
+    token::TokenKind,
+    value::SemanticValue,
+};
+
+//Inline nonterminals (`#[inline] BinOp -> Plus | Minus;`) are substituted into
+//the bodies that use them before the automaton is built, so they add no state
+//and no conflict of their own. `Expr -> Expr BinOp Expr` becomes
+//`Expr -> Expr Plus Expr | Expr Minus Expr`.
+impl<AST, Token, TranslatorStack> Grammar<AST, Token, TranslatorStack>
+where
+    AST: Clone + 'static,
+    Token: Clone + TokenKind + 'static,
+    TranslatorStack: Clone + 'static,
+{
+    pub fn mark_inline(&mut self, head: &str) {
+        let head = self.symbols.intern(Symbol::NONTERMINAL(head.to_string()));
+        self.inline.insert(head);
+    }
+
+    ///Replaces every use of an inline nonterminal by each of its alternatives
+    ///and drops the inline productions, called by LR1_Parser::try_new
+    pub fn expand_inline(&mut self) -> Result<(), GrammarError> {
+        if self.inline.is_empty() {
+            return Ok(());
+        }
+        if self.inline.contains(&START_SYMBOL_ID) {
+            return Err(GrammarError::InvalidInline(String::from(
+                "Start can not be inline",
+            )));
+        }
+        if let Some(recursive) = self.recursive_inline() {
+            return Err(GrammarError::InvalidInline(format!(
+                "inline nonterminal {} is recursive",
+                self.symbols.lookup(recursive).to_string()
+            )));
+        }
+
+        let mut alternatives: IndexMap<SymbolId, Vec<Production<AST, Token, TranslatorStack>>> =
+            IndexMap::new();
+        for production in self.productions.vec.iter() {
+            if self.inline.contains(&production.head) {
+                alternatives
+                    .entry(production.head)
+                    .or_default()
+                    .push(production.clone());
+            }
+        }
+        if let Some(head) = self
+            .inline
+            .iter()
+            .find(|head| !alternatives.contains_key(*head))
+        {
+            return Err(GrammarError::UndefinedNonterminals(vec![self
+                .symbols
+                .lookup(*head)
+                .to_string()]));
+        }
+
+        //predicates see the symbols of their own body, which inlining changes
+        if let Some(production) = self.productions.vec.iter().find(|production| {
+            production.predicate.is_some()
+                && (self.inline.contains(&production.head)
+                    || production
+                        .body
+                        .iter()
+                        .any(|symbol| self.inline.contains(symbol)))
+        }) {
+            return Err(GrammarError::InvalidInline(format!(
+                "predicate of {} can not be combined with inline nonterminals",
+                self.symbols.lookup(production.head).to_string()
+            )));
+        }
+
+        //the left context of a mid-rule action would change with the alternative
+        if let Some(head) = self.productions.vec.iter().find_map(|production| {
+            let mid_rule = production
+                .body
+                .iter()
+                .rposition(|symbol| self.mid_rules.contains_key(symbol))?;
+            production.body[..mid_rule]
+                .iter()
+                .find(|symbol| self.inline.contains(*symbol))
+        }) {
+            return Err(GrammarError::InvalidInline(format!(
+                "inline nonterminal {} is used before a mid-rule action",
+                self.symbols.lookup(*head).to_string()
+            )));
+        }
+
+        let mut productions = Productions::new();
+        let mut precedences = IndexMap::new();
+        for production in self.productions.vec.iter().skip(1) {
+            if self.inline.contains(&production.head) {
+                continue;
+            }
+            let precedence = self
+                .precedences
+                .productions
+                .get(&ProductionId(production.index))
+                .copied();
+            let mut pending = vec![production.clone()];
+            while let Some(production) = pending.pop() {
+                let position = production
+                    .body
+                    .iter()
+                    .position(|symbol| self.inline.contains(symbol));
+                let Some(position) = position else {
+                    let mut production = production;
+                    production.index = productions.vec.len();
+                    let id = productions.intern(production);
+                    if let Some(terminal) = precedence {
+                        precedences.insert(id, terminal);
+                    }
+                    continue;
+                };
+                //reversed so expansions keep the order of the alternatives
+                for inner in alternatives[&production.body[position]].iter().rev() {
+                    pending.push(self.inline_production(&production, position, inner));
+                }
+            }
+        }
+        self.productions = productions;
+        self.precedences.productions = precedences;
+        self.inline.clear();
+        Ok(())
+    }
+
+    //substitutes body[position] of outer by the body of inner
+    fn inline_production(
+        &self,
+        outer: &Production<AST, Token, TranslatorStack>,
+        position: usize,
+        inner: &Production<AST, Token, TranslatorStack>,
+    ) -> Production<AST, Token, TranslatorStack> {
+        let terminals =
+            |body: &[SymbolId]| body.iter().filter(|s| self.symbols.terminal(s)).count();
+        let symbols = position..position + inner.body.len();
+        let tokens_start = terminals(&outer.body[..position]);
+        let tokens = tokens_start..tokens_start + terminals(&inner.body);
+
+        let mut body = outer.body.clone();
+        body.splice(position..position + 1, inner.body.iter().copied());
+
+        let (symbols_start, symbols_end) = (symbols.start, symbols.end);
+        let (tokens_start, tokens_end) = (tokens.start, tokens.end);
+        let (inner_action, outer_action) = (&inner.action_tokens, &outer.action_tokens);
+        let (inner_value, outer_value) = (&inner.value_action_tokens, &outer.value_action_tokens);
+        Production {
+            index: 0,
+            head: outer.head,
+            body,
+            error_message: outer.error_message.clone(),
+            action: inline_action(
+                inner.action.clone(),
+                outer.action.clone(),
+                symbols.clone(),
+                tokens,
+            ),
+            action_tokens: quote! {
+                inline_action(#inner_action, #outer_action, #symbols_start..#symbols_end, #tokens_start..#tokens_end)
+            }
+            .into(),
+            value_action: inline_value_action(
+                inner.value_action.clone(),
+                outer.value_action.clone(),
+                symbols,
+            ),
+            value_action_tokens: quote! {
+                inline_value_action(#inner_value, #outer_value, #symbols_start..#symbols_end)
+            }
+            .into(),
+            argument_types: None,
+            value_type: outer.value_type.clone(),
+            predicate: None,
+            predicate_tokens: quote! {None}.into(),
+        }
+    }
+
+    fn recursive_inline(&self) -> Option<SymbolId> {
+        //inline nonterminals each one uses directly
+        let mut uses: IndexMap<SymbolId, IndexSet<SymbolId>> = IndexMap::new();
+        for production in self.productions.vec.iter() {
+            if self.inline.contains(&production.head) {
+                uses.entry(production.head).or_default().extend(
+                    production
+                        .body
+                        .iter()
+                        .filter(|symbol| self.inline.contains(*symbol)),
+                );
+            }
+        }
+        self.inline.iter().copied().find(|head| {
+            let mut reached: IndexSet<SymbolId> = IndexSet::new();
+            let mut pending = vec![*head];
+            while let Some(symbol) = pending.pop() {
+                for used in uses.get(&symbol).into_iter().flatten() {
+                    if used == head {
+                        return true;
+                    }
+                    if reached.insert(*used) {
+                        pending.push(*used);
+                    }
+                }
+            }
+            false
+        })
+    }
+}
+
+///Action of a production in which an inline nonterminal was substituted at
+///body[symbols], `tokens` being the tokens of the substituted body. The
+///inline action runs first on its own symbols, then the outer action sees the
+///substituted symbols as one symbol.
+pub fn inline_action<AST, Token, TranslatorStack>(
+    inner: Option<SemanticAction<AST, Token, TranslatorStack>>,
+    outer: Option<SemanticAction<AST, Token, TranslatorStack>>,
+    symbols: Range<usize>,
+    tokens: Range<usize>,
+) -> Option<SemanticAction<AST, Token, TranslatorStack>>
+where
+    AST: 'static,
+    Token: Clone + 'static,
+    TranslatorStack: 'static,
+{
+    if inner.is_none() && outer.is_none() {
+        return None;
+    }
+    Some(Arc::new(move |ctx| {
+        if let Some(inner) = &inner {
+            ctx.inner_context(symbols.clone(), tokens.clone(), |ctx| inner(ctx));
+        }
+        if let Some(outer) = &outer {
+            ctx.outer_context(symbols.clone(), tokens.clone(), |ctx| outer(ctx));
+        }
+    }))
+}
+
+///Typed action of a production in which an inline nonterminal was substituted
+///at body[symbols], the inline value replaces the values of those symbols
+pub fn inline_value_action(
+    inner: Option<ValueAction>,
+    outer: Option<ValueAction>,
+    symbols: Range<usize>,
+) -> Option<ValueAction> {
+    if inner.is_none() && outer.is_none() {
+        return None;
+    }
+    Some(Arc::new(move |mut values| {
+        let inner_values: Vec<SemanticValue> = values.drain(symbols.clone()).collect();
+        let value = match &inner {
+            Some(inner) => inner(inner_values)?,
+            None => Box::new(()),
+        };
+        values.insert(symbols.start, value);
+        match &outer {
+            Some(outer) => outer(values),
+            None => Ok(Box::new(())),
+        }
+    }))
+}
@@ -1,20 +1,37 @@
 #![allow(nonstandard_style)]
 
+//lets the derive macros refer to ::manodae inside this crate too
+extern crate self as manodae;
+
 pub mod action;
+pub mod bison;
+pub mod builder;
 pub mod codegen;
+pub mod compose;
 pub mod conflict;
+pub mod context;
 pub mod error;
 pub mod first;
 pub mod follow;
 pub mod grammar;
+pub mod inline;
 pub mod interner;
 pub mod item;
+pub mod mid_rule;
+pub mod outcome;
 pub mod parser;
+pub mod precedence;
+pub mod predicate;
 pub mod production;
 pub mod render_table;
+pub mod repair;
+pub mod session;
+pub mod source;
 pub mod state;
 pub mod symbol;
+pub mod template;
 pub mod token;
+pub mod value;
 
 pub mod prelude {
     pub use crate::action::Action;
@@ -22,17 +39,26 @@ pub mod prelude {
     pub use crate::action::Action::ERROR as E;
     pub use crate::action::Action::REDUCE as R;
     pub use crate::action::Action::SHIFT as S;
+    pub use crate::builder::GrammarBuilder;
     pub use crate::codegen::Codegen;
+    pub use crate::context::ActionContext;
     pub use crate::error::ParseError;
     pub use crate::grammar;
     pub use crate::grammar::Grammar;
+    pub use crate::inline::inline_action;
+    pub use crate::inline::inline_value_action;
     pub use crate::interner::Interner;
     pub use crate::item::Item as I;
+    pub use crate::outcome::{InputStatus, ParseFailure, ParseOutcome, PushStatus};
     pub use crate::parser::LR1_Parser;
+    pub use crate::precedence::{Associativity, Precedence, Precedences};
+    pub use crate::predicate::{semantic_predicate, PredicateContext};
+    pub use crate::production::semantic_action;
     pub use crate::production::Production;
     pub use crate::production::Production as P;
     pub use crate::production::ProductionId as p;
     pub use crate::production::Productions;
+    pub use crate::source::{SourceToken, TokenIter, TokenResults, TokenSource};
     pub use crate::state::State as a;
     pub use crate::state::StateId as i;
     pub use crate::state::StateId;
@@ -43,10 +69,15 @@ pub mod prelude {
     pub use crate::symbol::SymbolId as s;
     pub use crate::symbol::SymbolId;
     pub use crate::symbol::Symbols;
+    pub use crate::template::{Template, TemplateAlternative};
+    pub use crate::value::take_value;
+    pub use crate::value::SemanticValue;
+    pub use crate::value::ValueType;
     pub use indexmap::IndexMap;
     pub use indexmap::IndexSet;
     pub use quote::quote;
     pub use std::rc::Rc;
+    pub use std::sync::Arc;
 }
 
 #[cfg(test)]
@@ -0,0 +1,75 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    grammar::Grammar,
+    interner::Interner,
+    production::{Production, SemanticAction},
+    symbol::{Symbol, SymbolId},
+    token::TokenKind,
+};
+
+//Mid-rule actions (`Block -> LBrace { |ctx| open(ctx) } Stmts RBrace`) run
+//before the production completes. Like yacc each one is desugared into an
+//empty production of a generated nonterminal, `Block -> LBrace Block@1 Stmts RBrace`
+//and `Block@1 -> ε`, whose action sees the symbols to its left.
+impl<AST, Token, TranslatorStack> Grammar<AST, Token, TranslatorStack>
+where
+    AST: Clone,
+    Token: Clone + TokenKind,
+    TranslatorStack: Clone,
+{
+    ///Inserts a generated nonterminal for each action, `position` being the
+    ///number of body symbols to its left. An action after the last symbol is
+    ///the action of the production itself and is returned.
+    #[allow(clippy::type_complexity)]
+    pub fn mid_rule_actions(
+        &mut self,
+        head: SymbolId,
+        body: Vec<SymbolId>,
+        actions: Vec<(
+            usize,
+            SemanticAction<AST, Token, TranslatorStack>,
+            TokenStream,
+        )>,
+    ) -> (
+        Vec<SymbolId>,
+        Option<(SemanticAction<AST, Token, TranslatorStack>, TokenStream)>,
+    ) {
+        let head_name = self.symbols.lookup(head).to_string();
+        let original = body.clone();
+        let mut body = body;
+        let mut last = None;
+        for (inserted, (position, action, action_tokens)) in actions.into_iter().enumerate() {
+            if position == original.len() {
+                last = Some((action, action_tokens));
+                continue;
+            }
+            let symbol = Symbol::NONTERMINAL(format!("{head_name}@{}", self.mid_rules.len() + 1));
+            let symbol = self.symbols.intern(symbol);
+            let left = position + inserted;
+            let terminals = original[..position]
+                .iter()
+                .filter(|symbol| self.symbols.terminal(symbol))
+                .count();
+            self.mid_rules.insert(symbol, (left, terminals));
+            body.insert(left, symbol);
+            let production = Production {
+                head: symbol,
+                body: vec![],
+                error_message: None,
+                action: Some(action),
+                action_tokens: action_tokens.into(),
+                value_action: None,
+                value_action_tokens: quote! {None}.into(),
+                argument_types: None,
+                value_type: None,
+                predicate: None,
+                predicate_tokens: quote! {None}.into(),
+                index: self.productions.vec.len(),
+            };
+            self.productions.intern(production);
+        }
+        (body, last)
+    }
+}
@@ -0,0 +1,75 @@
+use std::{error::Error, fmt::Display, ops::Range};
+
+use crate::value::SemanticValue;
+
+///What the parser did, the same whether the input is accepted or not
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseStats {
+    //tokens shifted
+    pub tokens: usize,
+    pub reductions: usize,
+    //syntax errors recovered from, reported or not
+    pub recoveries: usize,
+    //tokens discarded while recovering
+    pub discarded: usize,
+}
+
+///Input accepted by the parser, possibly after recovering from errors
+#[derive(Debug)]
+pub struct ParseOutcome {
+    ///value of Start, None when panic mode recovered an error as the values
+    ///are incomplete
+    pub value: Option<SemanticValue>,
+    pub recovered: bool,
+    pub stats: ParseStats,
+}
+
+///What a token pushed to a ParseSession left the parser with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushStatus {
+    ///the input so far is the prefix of a longer one, more input is needed
+    Incomplete,
+    ///the input so far is complete, finish would accept it. More input may
+    ///still follow.
+    Complete,
+    ///the token was a syntax error, the errors are in the list given to the
+    ///session
+    Error,
+    ///the token was discarded while recovering from an earlier error, no new
+    ///error was reported
+    Recovering,
+}
+
+///How the input of LR1_Parser::parse_interactive ended, for REPLs that show
+///a continuation prompt on incomplete input
+#[derive(Debug)]
+pub enum InputStatus {
+    ///the input is valid, it was accepted without errors
+    Accepted(ParseOutcome),
+    ///the input is valid so far but ended where more was expected, ex:
+    ///`let x = (1 +`. No error is reported for it.
+    Incomplete(ParseStats),
+    ///the input has syntax errors, they are in the list given to the parser
+    Invalid(ParseStats),
+}
+
+///Input the parser gave up on, recovery reached the end of the input. The
+///errors are in the list given to parse.
+#[derive(Debug, Clone)]
+pub struct ParseFailure {
+    //span of the token the parser stopped at
+    pub span: Range<usize>,
+    pub stats: ParseStats,
+}
+
+impl Display for ParseFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "parse abandoned at {}..{} after {} errors",
+            self.span.start, self.span.end, self.stats.recoveries
+        )
+    }
+}
+
+impl Error for ParseFailure {}
@@ -1,19 +1,27 @@
-use std::{cell::RefCell, fmt::Debug, process::exit, rc::Rc};
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
 
 use indexmap::{IndexMap, IndexSet};
-use logos::{Lexer, Logos, Span};
+use logos::Span;
 
 use crate::{
     action::Action,
-    error::ParseError,
-    first::compute_first_set,
+    error::{GrammarError, ParseError, ParseErrorKind, Repair},
+    first::{compute_first_set, compute_nullable_set, first_of_sequence},
     follow::compute_follow_set,
     grammar::Grammar,
     interner::Interner,
     item::{Item, ItemVecExtension},
-    production::{ProductionId, AUGMENT_PRODUCTION_ID},
-    state::{State, StateId, StateVecExtension, States},
-    symbol::{Symbol, SymbolId, AUGMENT_START_SYMBOL_ID, EOF_SYMBOL_ID},
+    outcome::{InputStatus, ParseFailure, ParseOutcome},
+    precedence::Associativity,
+    predicate::PredicateContext,
+    production::{Production, ProductionId, AUGMENT_PRODUCTION_ID},
+    session::ParseSession,
+    source::{SourceToken, TokenSource},
+    state::{State, StateId, StateNode, StateVecExtension, States},
+    symbol::{
+        Symbol, SymbolId, Symbols, AUGMENT_START_SYMBOL_ID, EOF_SYMBOL_ID, ERROR_SYMBOL_ID,
+        START_SYMBOL_ID,
+    },
     token::TokenKind,
 };
 
@@ -23,28 +31,64 @@ pub struct LR1_Parser<AST, Token, TranslatorStack> {
     pub LR1_automata: States,
     pub follow_set: IndexMap<SymbolId, IndexSet<SymbolId>>,
     pub first_set: IndexMap<SymbolId, IndexSet<SymbolId>>,
+    //used only when constructing table, no need for parsing
+    pub nullable: IndexSet<SymbolId>,
     pub conflicts: bool,
     pub goto: IndexMap<StateId, IndexMap<SymbolId, StateId>>,
     pub action: IndexMap<StateId, IndexMap<SymbolId, Action>>,
+    //conflicting actions involving a predicate, tried in order at parse time
+    pub predicated_actions: IndexMap<StateId, IndexMap<SymbolId, Vec<Action>>>,
+    //terminal symbol of every token variant, indexed by TokenKind::terminal_id
+    pub terminal_table: Vec<Option<SymbolId>>,
+    //token variants the grammar never refers to
+    pub warnings: Vec<String>,
+    ///whether the repair of a syntax error replaces the input instead of
+    ///the error recovery, false by default
+    pub repair_input: bool,
     //used only when constructing table, no need for parsing
     pub item_closure_map: IndexMap<Item, Vec<Item>>,
     //used only when constructing table, no need for parsing
     pub closure_map: IndexMap<Vec<Item>, Vec<Item>>,
 }
 
-impl<'a, AST, Token, TranslatorStack> LR1_Parser<AST, Token, TranslatorStack>
+impl<AST, Token, TranslatorStack> LR1_Parser<AST, Token, TranslatorStack>
 where
-    AST: Clone + Debug + PartialEq,
-    Token: ToString + Debug + Clone + PartialEq + TokenKind + Logos<'a>,
-    TranslatorStack: Clone + Debug + PartialEq,
+    AST: Clone + Debug + PartialEq + 'static,
+    Token: ToString + Debug + Clone + PartialEq + TokenKind + 'static,
+    TranslatorStack: Clone + Debug + PartialEq + 'static,
 {
+    ///Builds the parser of a grammar known to be valid.
+    ///
+    ///# Panics
+    ///
+    ///Panics with the GrammarError try_new would return: terminals missing
+    ///from the Token type, mismatched value types or recursive inline
+    ///nonterminals. Use try_new for grammars built at runtime.
     pub fn new(
         grammar: Grammar<AST, Token, TranslatorStack>,
     ) -> LR1_Parser<AST, Token, TranslatorStack> {
-        //collect all grammar symbols without duplicates
+        Self::try_new(grammar).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    ///Builds the parser after cross-checking grammar terminals against the
+    ///TokenKind variants: unknown terminals are an error, variants never used
+    ///by the grammar end up in `warnings`. Typed actions that don't match the
+    ///value types of their symbols are an error too.
+    pub fn try_new(
+        grammar: Grammar<AST, Token, TranslatorStack>,
+    ) -> Result<LR1_Parser<AST, Token, TranslatorStack>, GrammarError> {
+        let mut grammar = grammar;
+        if let Some(error) = grammar.errors.first() {
+            return Err(error.clone());
+        }
+        //inline expansion merges typed actions, they are checked as written
+        grammar.check_value_types()?;
+        grammar.expand_inline()?;
+        let warnings = check_terminals::<Token>(&grammar.symbols)?;
 
         let first_set = compute_first_set(&grammar);
         let follow_set = compute_follow_set(&grammar);
+        let nullable = compute_nullable_set(&grammar);
 
         let mut production_head_map: IndexMap<SymbolId, IndexSet<ProductionId>> = IndexMap::new();
 
@@ -57,20 +101,39 @@ where
                 .or_insert(IndexSet::from([*id]));
         });
 
+        //the error variant of the lexer stands for the reserved error terminal,
+        //unless the grammar uses it as a terminal of its own
+        let error: Token = Token::error().into();
+        let terminal_table = Token::terminal_names()
+            .iter()
+            .enumerate()
+            .map(|(terminal_id, name)| {
+                grammar
+                    .symbols
+                    .reverse_lookup(&Symbol::TERMINAL(name.to_string()))
+                    .or_else(|| (terminal_id == error.terminal_id()).then_some(ERROR_SYMBOL_ID))
+            })
+            .collect();
+
         let mut a = LR1_Parser {
             grammar,
             LR1_automata: States::new(),
             first_set,
             follow_set,
+            nullable,
             conflicts: false,
             action: IndexMap::new(),
+            predicated_actions: IndexMap::new(),
             goto: IndexMap::new(),
+            terminal_table,
+            warnings,
+            repair_input: false,
             item_closure_map: IndexMap::new(),
             closure_map: IndexMap::new(),
         };
         a.grammar.production_head_map = production_head_map;
         a.construct_LALR_Table();
-        a
+        Ok(a)
     }
 
     // Algorithm
@@ -96,12 +159,17 @@ where
                 } else {
                     let B = item.next_symbol(&self.grammar.productions);
                     let production = self.grammar.productions.lookup(item.production);
-                    let beta = production.body.get((item.cursor + 1) as usize);
-                    let first_of = if let Some(beta) = beta {
-                        vec![beta.clone()]
-                    } else {
-                        item.lookaheads.clone()
-                    };
+                    //FIRST(𝛽𝑎), 𝛽 may be empty or nullable
+                    let beta = production
+                        .body
+                        .get((item.cursor + 1) as usize..)
+                        .unwrap_or_default();
+                    let (first_beta, beta_nullable) =
+                        first_of_sequence(&self.first_set, &self.nullable, beta);
+                    let mut first_of: Vec<SymbolId> = first_beta.into_iter().collect();
+                    if beta_nullable {
+                        first_of.extend(item.lookaheads.iter().copied());
+                    }
                     if let None = B {
                         continue;
                     }
@@ -112,9 +180,9 @@ where
                     let b_productions: &IndexSet<_> =
                         self.grammar.production_head_map.get(&B).unwrap();
                     let mut lookaheads = Vec::new();
-                    for first in first_of.iter() {
-                        for terminal_b in self.first_set.get(first).unwrap().iter() {
-                            lookaheads.push(*terminal_b);
+                    for terminal_b in first_of.into_iter() {
+                        if !lookaheads.contains(&terminal_b) {
+                            lookaheads.push(terminal_b);
                         }
                     }
                     let mut ni = vec![];
@@ -148,11 +216,11 @@ where
     // }
     fn goto(
         &mut self,
-        state_: &Rc<RefCell<State>>,
+        state_: &Rc<RefCell<StateNode>>,
         symbol: SymbolId,
-    ) -> Option<Rc<RefCell<State>>> {
+    ) -> Option<Rc<RefCell<StateNode>>> {
         let mut new_items = vec![];
-        for item in state_.borrow().items.iter() {
+        for item in state_.borrow().state.items.iter() {
             let item_symbol = item.next_symbol(&self.grammar.productions);
             if item_symbol.is_none() {
                 continue;
@@ -177,7 +245,7 @@ where
                 .insert(transition_productions.clone(), new_items.clone());
         }
         let state = State::new(0, new_items, symbol);
-        Some(Rc::new(RefCell::new(state)))
+        Some(Rc::new(RefCell::new(StateNode::new(state))))
     }
 
     // Algorithm
@@ -190,7 +258,7 @@ where
     //                   add 𝐺𝑂𝑇𝑂(𝐼, 𝑋) to 𝐶;
     //   until no new sets of items are added to 𝐶;
     // }
-    fn items(&mut self) {
+    fn items(&mut self) -> Vec<Rc<RefCell<StateNode>>> {
         let augmented_item: Item = Item {
             production: AUGMENT_PRODUCTION_ID,
             cursor: 0,
@@ -198,13 +266,11 @@ where
         };
         let mut S0_items = vec![augmented_item];
         self.clousure(&mut S0_items);
-        let mut LR1_automata = vec![Rc::new(RefCell::new(State {
+        let mut LR1_automata = vec![Rc::new(RefCell::new(StateNode::new(State {
             transition_symbol: AUGMENT_START_SYMBOL_ID, // Dummy symbol
             index: 0,
             items: S0_items,
-            outgoing: IndexMap::new(),
-            incoming: vec![],
-        }))];
+        })))];
         let mut goto_set = IndexMap::new();
         let mut states_count = 0;
         let mut states_iterated_count = 0;
@@ -218,7 +284,7 @@ where
                 let state = LR1_automata.get(states_index).unwrap();
                 let mut goto_map = IndexMap::new();
                 for symbol in symbols.iter() {
-                    let items = state.borrow().items.clone();
+                    let items = state.borrow().state.items.clone();
                     let es = goto_set.get(&(items.clone(), symbol));
                     if let Some(es) = es {
                         goto_map.insert(*symbol, Rc::clone(es));
@@ -242,13 +308,14 @@ where
         LR1_automata
             .iter_mut()
             .enumerate()
-            .for_each(|(index, state)| state.borrow_mut().index = index);
+            .for_each(|(index, state)| state.borrow_mut().state.index = index);
 
         LR1_automata.merge_sets();
 
         LR1_automata.iter().for_each(|state| {
-            self.LR1_automata.intern(state.borrow().clone());
+            self.LR1_automata.intern(state.borrow().state.clone());
         });
+        LR1_automata
     }
 
     // Algorithm
@@ -275,28 +342,37 @@ where
     //  7. Then intitial state of the parser is the one constructed from the set
     //     of items containing [𝑆' → .𝑆,$]
     pub fn construct_LALR_Table(&mut self) {
-        self.items();
+        //the transitions are only needed here, the parser keeps the states
+        let LR1_automata = self.items();
 
         let mut action: IndexMap<StateId, IndexMap<SymbolId, Action>> = IndexMap::new();
 
         let mut goto: IndexMap<StateId, IndexMap<SymbolId, StateId>> = IndexMap::new();
 
-        for state in self.LR1_automata.vec.iter() {
-            let state_id = self.LR1_automata.reverse_lookup(state).unwrap();
-            for item in state.items.iter() {
+        let mut predicated_actions: IndexMap<StateId, IndexMap<SymbolId, Vec<Action>>> =
+            IndexMap::new();
+
+        let mut conflicts = false;
+        for node in LR1_automata.iter() {
+            let state = node.borrow();
+            let state_id = self.LR1_automata.reverse_lookup(&state.state).unwrap();
+            for item in state.state.items.iter() {
                 let next_symbol = item.next_symbol(&self.grammar.productions);
                 if next_symbol.is_none() {
+                    let map = action.entry(state_id).or_default();
+                    let predicated = predicated_actions.entry(state_id).or_default();
                     if item.production.ne(&AUGMENT_PRODUCTION_ID) {
-                        let mut map = IndexMap::new();
-                        item.lookaheads.iter().for_each(|lookahead| {
-                            map.insert(*lookahead, Action::REDUCE(item.production.clone()));
-                        });
-
-                        action.entry(state_id).insert_entry(map);
+                        for lookahead in item.lookaheads.iter() {
+                            conflicts |= self.insert_action(
+                                map,
+                                predicated,
+                                *lookahead,
+                                Action::REDUCE(item.production),
+                            );
+                        }
                     } else {
-                        action
-                            .entry(state_id)
-                            .insert_entry(IndexMap::from([(EOF_SYMBOL_ID, Action::ACCEPT)]));
+                        conflicts |=
+                            self.insert_action(map, predicated, EOF_SYMBOL_ID, Action::ACCEPT);
                     }
                     continue;
                 }
@@ -310,18 +386,13 @@ where
                 let item_goto_state = item_goto_state.unwrap();
                 let goto_state_id = self
                     .LR1_automata
-                    .reverse_lookup(&*item_goto_state.borrow())
+                    .reverse_lookup(&item_goto_state.borrow().state)
                     .unwrap();
                 if self.grammar.symbols.terminal(&symbol) {
-                    action
-                        .entry(state_id)
-                        .and_modify(|map| {
-                            map.insert(symbol.clone(), Action::SHIFT(goto_state_id));
-                        })
-                        .or_insert(IndexMap::from([(
-                            symbol.clone(),
-                            Action::SHIFT(goto_state_id),
-                        )]));
+                    let map = action.entry(state_id).or_default();
+                    let predicated = predicated_actions.entry(state_id).or_default();
+                    conflicts |=
+                        self.insert_action(map, predicated, symbol, Action::SHIFT(goto_state_id));
                 }
                 if self.grammar.symbols.non_terminal(&symbol) {
                     goto.entry(state_id)
@@ -332,8 +403,173 @@ where
                 }
             }
         }
+        predicated_actions.retain(|_, row| !row.is_empty());
         self.action = action;
+        self.predicated_actions = predicated_actions;
         self.goto = goto;
+        self.conflicts = conflicts;
+    }
+
+    //Adds an action to a state's row, shift/reduce conflicts are resolved by
+    //precedence like yacc does. Returns true on an unresolved conflict, in
+    //which case shift wins over reduce and the earlier production wins a
+    //reduce/reduce conflict. Conflicts involving a predicate are kept in
+    //`predicated` and decided at parse time, predicated actions first.
+    fn insert_action(
+        &self,
+        map: &mut IndexMap<SymbolId, Action>,
+        predicated: &mut IndexMap<SymbolId, Vec<Action>>,
+        symbol: SymbolId,
+        action: Action,
+    ) -> bool {
+        let existing = match map.get(&symbol) {
+            None => {
+                map.insert(symbol, action);
+                return false;
+            }
+            Some(existing) if *existing == action => return false,
+            Some(existing) => existing.clone(),
+        };
+        if predicated.contains_key(&symbol) || self.guarded(&existing) || self.guarded(&action) {
+            let candidates = predicated.entry(symbol).or_insert(vec![existing]);
+            if !candidates.contains(&action) {
+                candidates.push(action);
+            }
+            candidates.sort_by_key(|action| !self.guarded(action));
+            map.insert(symbol, candidates[0].clone());
+            //only the first action without a predicate can ever be taken
+            return candidates
+                .iter()
+                .filter(|action| !self.guarded(action))
+                .count()
+                > 1;
+        }
+        if let Some(resolved) = self.resolve_by_precedence(symbol, &existing, &action) {
+            map.insert(symbol, resolved);
+            return false;
+        }
+        let keep = match (&existing, &action) {
+            (Action::REDUCE(_), Action::SHIFT(_)) => action,
+            (Action::REDUCE(a), Action::REDUCE(b)) if b.0 < a.0 => action,
+            _ => existing,
+        };
+        map.insert(symbol, keep);
+        true
+    }
+
+    //true if the action can be refused by a predicate at parse time, a shift
+    //is refused when the predicates of every production it continues fail
+    fn guarded(&self, action: &Action) -> bool {
+        match action {
+            Action::REDUCE(production) => self.grammar.productions.vec[production.0]
+                .predicate
+                .is_some(),
+            Action::SHIFT(state) => {
+                let mut kernel = self.LR1_automata.vec[state.0]
+                    .items
+                    .iter()
+                    .filter(|item| item.cursor > 0)
+                    .peekable();
+                kernel.peek().is_some()
+                    && kernel.all(|item| {
+                        self.grammar.productions.vec[item.production.0]
+                            .predicate
+                            .is_some()
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    fn resolve_by_precedence(
+        &self,
+        symbol: SymbolId,
+        existing: &Action,
+        action: &Action,
+    ) -> Option<Action> {
+        let (shift, reduce, production) = match (existing, action) {
+            (Action::SHIFT(_), Action::REDUCE(production)) => (existing, action, production),
+            (Action::REDUCE(production), Action::SHIFT(_)) => (action, existing, production),
+            _ => return None,
+        };
+        let precedences = &self.grammar.precedences;
+        let terminal = precedences.terminals.get(&symbol)?;
+        let production = precedences.production(
+            &self.grammar.productions.vec[production.0],
+            &self.grammar.symbols,
+        )?;
+        let resolved = if production.level > terminal.level {
+            reduce.clone()
+        } else if production.level < terminal.level {
+            shift.clone()
+        } else {
+            match terminal.associativity {
+                Associativity::Left => reduce.clone(),
+                Associativity::Right => shift.clone(),
+                Associativity::NonAssoc => Action::ERROR(format!(
+                    "{} is non associative",
+                    self.grammar.symbols.lookup(symbol).to_string()
+                )),
+            }
+        };
+        Some(resolved)
+    }
+
+    //First action of the state's row for symbol whose predicates hold, ctx
+    //holds the whole stacks
+    pub(crate) fn predicated_action(
+        &self,
+        state: StateId,
+        symbol: SymbolId,
+        action: &Action,
+        ctx: &PredicateContext<AST, Token>,
+    ) -> Option<Action> {
+        let candidates = match self
+            .predicated_actions
+            .get(&state)
+            .and_then(|row| row.get(&symbol))
+        {
+            Some(candidates) => candidates.as_slice(),
+            None => std::slice::from_ref(action),
+        };
+        candidates
+            .iter()
+            .filter(|action| !matches!(action, Action::ERROR(_)))
+            .find(|action| match action {
+                Action::REDUCE(production) => {
+                    let production = &self.grammar.productions.vec[production.0];
+                    self.predicate_holds(production, production.body_len(), ctx)
+                }
+                //any production continued by the shift may hold
+                Action::SHIFT(next) => self.LR1_automata.vec[next.0]
+                    .items
+                    .iter()
+                    .filter(|item| item.cursor > 0)
+                    .any(|item| {
+                        let production = &self.grammar.productions.vec[item.production.0];
+                        self.predicate_holds(production, item.cursor as usize - 1, ctx)
+                    }),
+                _ => true,
+            })
+            .cloned()
+    }
+
+    //evaluates the predicate of production on its `parsed` symbols on top of the stacks
+    fn predicate_holds(
+        &self,
+        production: &Production<AST, Token, TranslatorStack>,
+        parsed: usize,
+        ctx: &PredicateContext<AST, Token>,
+    ) -> bool {
+        let Some(predicate) = &production.predicate else {
+            return true;
+        };
+        predicate(&PredicateContext {
+            ast: ctx.ast,
+            spans: &ctx.spans[ctx.spans.len() - parsed..],
+            values: &ctx.values[ctx.values.len() - parsed..],
+            lookahead: ctx.lookahead,
+        })
     }
 
     //LR-Parsing Algorithm
@@ -362,205 +598,243 @@ where
     //          call error-recovery routine;
     //      }
     // }
+    //
+    //Alongside the state stack a value stack holds the semantic value of every
+    //grammar symbol on the stack, returns the value of Start if input is accepted.
+    //A span stack holds the span of every grammar symbol on the stack, a reduce
+    //hands its action only the tokens shifted by the production body and
+    //removes them from the input token stack
+    //
+    //Syntax errors are recovered with error productions like yacc: states are
+    //popped until one shifts the error terminal, which is shifted with the
+    //token the error was detected on, and input is discarded until a token the
+    //parser can act on. Lexer errors and tokens the grammar never uses are
+    //syntax errors too.
+    //Grammars without a usable error production fall back to panic mode
+    //(Dragon book 4.8.3): states are popped until one has a goto on the head
+    //of a production the error interrupted, input is skipped until a token of
+    //its FOLLOW set and parsing resumes as if the nonterminal had been reduced.
+    //No value is returned once panic mode recovered an error. Errors are pushed
+    //to `errors` as they are found, the result tells whether the input was
+    //accepted and what the parser did. Tokens are read from any TokenSource,
+    //a logos lexer among others.
     pub fn parse(
-        &mut self,
-        mut lexer: Lexer<'a, Token>,
+        &self,
+        mut source: impl TokenSource<Token>,
         errors: &mut Vec<ParseError>,
         ast: &mut AST,
-    ) {
-        let mut stack: Vec<StateId> = vec![];
-        let mut current_input = if let Some(Ok(input)) = lexer.next() {
-            input
-        } else {
-            return;
-        };
-        let mut previous_input = current_input.clone();
-        let mut current_input_symbol = Symbol::TERMINAL(current_input.to_string());
-        let mut S0 = self.LR1_automata.map.first().unwrap().1;
-        let mut translator_stack: Vec<TranslatorStack> = Vec::new();
-        let mut input_token_stack: Vec<(Token, Span)> = Vec::new();
-
-        stack.push(*S0);
-        loop {
-            S0 = stack.last().unwrap();
-            //every state will be in action_map so unwrap
-            let action_map = self.action.get(S0).unwrap();
-            let symbol_id = self.grammar.symbols.reverse_lookup(&current_input_symbol);
-            if symbol_id.is_none() {
-                return;
+    ) -> Result<ParseOutcome, ParseFailure> {
+        ParseSession::with_source(self, &mut source, errors, ast).run()
+    }
+
+    ///Parses `source` like parse, but an input that ends where more is
+    ///expected, with no error before, is Incomplete rather than a syntax
+    ///error. That is the end of the input coming in a state with no action
+    ///on EOF but with continuations on other terminals.
+    pub fn parse_interactive(
+        &self,
+        mut source: impl TokenSource<Token>,
+        errors: &mut Vec<ParseError>,
+        ast: &mut AST,
+    ) -> InputStatus {
+        ParseSession::with_source(self, &mut source, errors, ast).run_interactive()
+    }
+
+    ///Session that is pushed the tokens one at a time instead of reading a
+    ///token source, for input that arrives piece by piece. Actions have no
+    ///lexer in it: ActionContext::extras is None and relex_lookahead false.
+    pub fn session<'s>(
+        &'s self,
+        errors: &'s mut Vec<ParseError>,
+        ast: &'s mut AST,
+    ) -> ParseSession<'s, AST, Token, TranslatorStack> {
+        ParseSession::new(self, errors, ast)
+    }
+
+    //Innermost state of the stack with a shift on the error terminal, returns
+    //how many states to keep and the state the shift goes to
+    pub(crate) fn error_shift(&self, stack: &[StateId]) -> Option<(usize, StateId)> {
+        (1..=stack.len()).rev().find_map(|depth| {
+            match self.action[&stack[depth - 1]].get(&ERROR_SYMBOL_ID) {
+                Some(Action::SHIFT(state)) => Some((depth, *state)),
+                _ => None,
             }
-            if let Some(action) = action_map.get(&symbol_id.unwrap()) {
-                match action {
-                    Action::SHIFT(stateId) => {
-                        stack.push(*stateId);
-
-                        //To maintain current input as a stack helps library user;
-                        input_token_stack.push((current_input.clone(), lexer.span()));
-
-                        previous_input = current_input.clone();
-                        if let Some(i_result) = lexer.next() {
-                            if let Ok(input) = i_result {
-                                current_input = input;
-                                current_input_symbol = Symbol::TERMINAL(current_input.to_string());
-                            } else {
-                                return;
-                            }
-                        } else {
-                            current_input_symbol = Symbol::TERMINAL("EOF".to_string());
-                        };
-                    }
-                    Action::REDUCE(productionId) => {
-                        let production = self.grammar.productions.lookup(*productionId);
-                        match &production.action {
-                            Some(action) => (action.as_ref())(
-                                ast,
-                                &mut input_token_stack,
-                                &mut translator_stack,
-                                errors,
-                            ),
-                            None => {}
-                        };
-                        stack.truncate(stack.len() - production.body_len());
-                        let stack_top = stack.last().unwrap();
-                        let goto_map = self.goto.get(stack_top).unwrap();
-                        let goto_stack = goto_map.get(&production.head);
-                        if let Some(goto_stack) = goto_stack {
-                            stack.push(*goto_stack);
-                        }
-                    }
-                    Action::ACCEPT => {
-                        break;
-                    }
-                    _ => {}
-                }
-            } else {
-                let error_token = current_input;
-                //error recovery
-                //implement second method in this paper https://ieeexplore.ieee.org/document/6643853
-                //@todo need to optimise
-                let s0_state = self.LR1_automata.lookup(*S0);
-                let mut error_message = self.counstruct_syntax_error_message(S0);
-
-                let deduced_items = s0_state.transistion_productions(&self.grammar.productions);
-
-                if deduced_items.len() == 1 {
-                    let item = deduced_items.first().unwrap();
-                    let production = self.grammar.productions.lookup(item.production);
-                    if production.error_message.is_some() {
-                        error_message = production.error_message.unwrap().clone();
-                    }
-                }
+        })
+    }
 
-                if current_input_symbol == Symbol::TERMINAL(Token::eof().to_string()) {
-                    errors.push(ParseError {
-                        span: lexer.span(),
-                        message: error_message.clone(),
-                        production_end: true,
-                    });
-                } else {
-                    errors.push(ParseError {
-                        span: lexer.span(),
-                        message: error_message,
-                        production_end: false,
-                    });
-                }
+    //Innermost state of the stack with a goto on the head of a production the
+    //error interrupted, returns how many states to keep and that head
+    pub(crate) fn recovery_point(&self, stack: &[StateId]) -> Option<(usize, SymbolId)> {
+        let error_state = &self.LR1_automata.vec[stack.last()?.0];
+        let heads: IndexSet<SymbolId> = error_state
+            .items
+            .iter()
+            .filter(|item| item.cursor > 0)
+            .map(|item| self.grammar.productions.vec[item.production.0].head)
+            .filter(|head| *head != AUGMENT_START_SYMBOL_ID)
+            .collect();
+        (1..=stack.len()).rev().find_map(|depth| {
+            let goto = self.goto.get(&stack[depth - 1])?;
+            //recovering with Start skips the rest of the input, it comes last
+            heads
+                .iter()
+                .filter(|head| goto.contains_key(*head))
+                .min_by_key(|head| **head == START_SYMBOL_ID)
+                .map(|head| (depth, *head))
+        })
+    }
 
-                return;
-
-                // let mut deduced_production: Option<Production<AST, Token, TranslatorStack>> = None;
-                // loop {
-                //     stack.pop();
-                //     let so_o = stack.last();
-                //     if let None = so_o {
-                //         break;
-                //     }
-                //     S0 = so_o.unwrap();
-                //     let goto_map = self.goto.get(S0).unwrap();
-                //     let keys: Vec<SymbolId> = goto_map.clone().into_keys().collect();
-                //     let mut contains = false;
-                //     for item in deduced_items.iter() {
-                //         let production = self.grammar.productions.lookup(item.production);
-                //         if keys.contains(&production.head) {
-                //             contains = true;
-                //             deduced_production = Some(production);
-                //             break;
-                //         }
-                //     }
-                //     if contains {
-                //         break;
-                //     }
-                // }
-                // //skip input till input character contains in followset of ...
-                // //top_state transition symbol
-                // if let None = deduced_production {
-                //     break;
-                // }
-                // let error_production_follow_set = self
-                //     .follow_set
-                //     .get(&deduced_production.clone().unwrap().head.clone())
-                //     .unwrap();
-                // loop {
-                //     let symbol_id = self.grammar.symbols.reverse_lookup(&current_input_symbol);
-                //     if symbol_id.is_none() {
-                //         panic!("symbol not found");
-                //     }
-                //     if error_production_follow_set.contains(&symbol_id.unwrap()) {
-                //         if deduced_production.clone().unwrap().error_message.is_some() {
-                //             //let a = deduced_production.unwrap().clone(); todo
-                //             error_message =
-                //                 deduced_production.clone().unwrap().error_message.unwrap();
-                //         }
-                //         if input_symbol_skip_count == 0 {
-                //             errors.push(ParseError {
-                //                 token: previous_input.clone(),
-                //                 message: error_message,
-                //                 production_end: true,
-                //             });
-                //         } else {
-                //             errors.push(ParseError {
-                //                 token: error_token.clone(),
-                //                 message: error_message,
-                //                 production_end: false,
-                //             });
-                //         }
-                //         break;
-                //     } else {
-                //         input_symbol_skip_count += 1;
-                //         previous_input = current_input;
-                //         let ci_o = input_iter.next();
-                //         if let None = ci_o {
-                //             break;
-                //         }
-                //         current_input = ci_o.unwrap();
-                //         current_input_symbol = Symbol::TERMINAL(current_input.to_string());
-                //     }
-                // }
+    //terminal of a token read from the source. Lexer errors become the error
+    //token and, like tokens the grammar never uses, the error terminal, which
+    //is never shifted: they are syntax errors.
+    pub(crate) fn input_symbol(&self, input: SourceToken<Token>) -> (SymbolId, Token, Span) {
+        match input {
+            Ok((token, span)) => {
+                let symbol = self.terminal_symbol(&token).unwrap_or(ERROR_SYMBOL_ID);
+                (symbol, token, span)
             }
+            Err(span) => (ERROR_SYMBOL_ID, Token::error().into(), span),
         }
     }
 
-    fn counstruct_syntax_error_message(&self, state: &StateId) -> String {
-        let action_map = self.action.get(state).unwrap();
-        let keys: Vec<Symbol> = action_map
-            .clone()
-            .into_keys()
+    //error on `token`, the first of `window`, with the repair the window allows
+    pub(crate) fn syntax_error(
+        &self,
+        stack: &[StateId],
+        token: &Token,
+        slice: Option<String>,
+        window: &[(SymbolId, Span)],
+        repair: Vec<Repair>,
+    ) -> ParseError {
+        let state = stack.last().unwrap();
+        let (symbol, span) = window[0].clone();
+        let items: Vec<Item> = self
+            .LR1_automata
+            .lookup(*state)
+            .transistion_productions(&self.grammar.productions)
             .into_iter()
-            .map(|symbol_id| self.grammar.symbols.lookup(symbol_id))
+            .cloned()
             .collect();
-        let action_keys: Vec<String> = keys.iter().map(|symbol| symbol.to_string()).collect();
-        String::from("Expected ") + join_either_or(action_keys).as_str()
+
+        //a production's message applies when it is the only one interrupted
+        let custom_message = match items.as_slice() {
+            [item] => self.grammar.productions.vec[item.production.0]
+                .error_message
+                .clone(),
+            _ => None,
+        };
+        //the error terminal stands for lexer errors and for tokens the
+        //grammar never uses, which are plain syntax errors
+        let kind = match symbol {
+            EOF_SYMBOL_ID => ParseErrorKind::UnexpectedEof,
+            ERROR_SYMBOL_ID if self.terminal_symbol(token).is_some() => ParseErrorKind::Lexical,
+            _ => ParseErrorKind::Syntax,
+        };
+        //tokens matched by name have no terminal id
+        let token_id = Some(token.terminal_id()).filter(|_| !Token::terminal_names().is_empty());
+        let (found, terminal_id) = if kind == ParseErrorKind::UnexpectedEof {
+            (Some(self.terminal_display_name(EOF_SYMBOL_ID)), None)
+        } else if let (ParseErrorKind::Lexical, Some(slice)) = (&kind, slice) {
+            (Some(slice), token_id)
+        } else {
+            let found = Token::terminal_display_names()
+                .get(token.terminal_id())
+                .map(|name| name.to_string())
+                .unwrap_or_else(|| token.to_string());
+            (Some(found), token_id)
+        };
+
+        ParseError {
+            kind,
+            span,
+            found,
+            terminal_id,
+            expected: self.expected_terminals(state),
+            state: Some(*state),
+            items,
+            custom_message,
+            production_end: symbol == EOF_SYMBOL_ID,
+            repair,
+            cascaded: false,
+        }
     }
-}
 
-fn join_either_or(items: Vec<String>) -> String {
-    match items.len() {
-        0 => "".to_string(),
-        1 => items[0].clone(),
-        2 => format!("{} or {}", items[0], items[1]),
-        _ => {
-            let all_but_last = &items[..items.len() - 1];
-            let last = &items[items.len() - 1];
-            format!("{} or {}", all_but_last.join(", "), last)
+    //dense lookup of the terminal of a token's variant, None if the grammar
+    //never uses the variant. Tokens with no terminal names are looked up by
+    //their name.
+    pub(crate) fn terminal_symbol(&self, token: &Token) -> Option<SymbolId> {
+        if Token::terminal_names().is_empty() {
+            let name = token.to_string();
+            return self
+                .grammar
+                .symbols
+                .reverse_lookup(&Symbol::TERMINAL(name.clone()))
+                .or_else(|| (name == Token::error().to_string()).then_some(ERROR_SYMBOL_ID));
         }
+        self.terminal_table
+            .get(token.terminal_id())
+            .copied()
+            .flatten()
+    }
+
+    //display names of the terminals `state` has an action on
+    fn expected_terminals(&self, state: &StateId) -> Vec<String> {
+        self.action[state]
+            .iter()
+            .filter(|(symbol_id, action)| {
+                **symbol_id != ERROR_SYMBOL_ID && !matches!(action, Action::ERROR(_))
+            })
+            .map(|(symbol_id, _)| self.terminal_display_name(*symbol_id))
+            .collect()
+    }
+
+    //terminals are shown by their token display name when the token has one
+    pub(crate) fn terminal_display_name(&self, symbol: SymbolId) -> String {
+        self.terminal_table
+            .iter()
+            .position(|terminal| *terminal == Some(symbol))
+            .and_then(|terminal_id| Token::terminal_display_names().get(terminal_id))
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| self.grammar.symbols.lookup(symbol).to_string())
+    }
+}
+
+//Every grammar terminal has to be named after a token variant, otherwise the
+//lexer can never produce it. The error and eof variants are never written in
+//grammars so they are not reported as unused, the reserved error terminal is
+//not a token.
+fn check_terminals<Token>(symbols: &Symbols) -> Result<Vec<String>, GrammarError>
+where
+    Token: TokenKind,
+{
+    let terminal_names = Token::terminal_names();
+    //tokens matched by name can't be listed
+    if terminal_names.is_empty() {
+        return Ok(vec![]);
+    }
+    let unknown: Vec<String> = symbols
+        .terminals
+        .iter()
+        .filter(|terminal| ![EOF_SYMBOL_ID, ERROR_SYMBOL_ID].contains(*terminal))
+        .map(|terminal| symbols.lookup(*terminal).to_string())
+        .filter(|name| !terminal_names.contains(&name.as_str()))
+        .collect();
+    if !unknown.is_empty() {
+        return Err(GrammarError::UnknownTerminals(unknown));
     }
+    let error: Token = Token::error().into();
+    let eof: Token = Token::eof().into();
+    let sentinels = [error.terminal_id(), eof.terminal_id()];
+    let warnings = terminal_names
+        .iter()
+        .enumerate()
+        .filter(|(terminal_id, _)| !sentinels.contains(terminal_id))
+        .filter(|(_, name)| {
+            symbols
+                .reverse_lookup(&Symbol::TERMINAL(name.to_string()))
+                .is_none()
+        })
+        .map(|(_, name)| format!("token {name} is never used by the grammar"))
+        .collect();
+    Ok(warnings)
 }
@@ -0,0 +1,80 @@
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+
+use crate::{
+    production::{Production, ProductionId},
+    symbol::{SymbolId, Symbols},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Associativity {
+    Left,
+    Right,
+    NonAssoc,
+}
+
+///Precedence of a terminal, like %left/%right/%nonassoc in yacc a higher
+///level binds tighter
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Precedence {
+    pub level: usize,
+    pub associativity: Associativity,
+}
+
+///Precedence declarations used to resolve shift/reduce conflicts
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Precedences {
+    pub terminals: IndexMap<SymbolId, Precedence>,
+    //productions taking their precedence from a given terminal, like %prec in yacc
+    pub productions: IndexMap<ProductionId, SymbolId>,
+}
+
+impl Hash for Precedences {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.terminals.iter().for_each(|entry| entry.hash(state));
+        self.productions.iter().for_each(|entry| entry.hash(state));
+    }
+}
+
+impl Precedences {
+    ///Declares terminals with the same precedence, each declaration binds
+    ///tighter than the previous ones
+    pub fn declare(&mut self, associativity: Associativity, terminals: &[SymbolId]) {
+        let level = self
+            .terminals
+            .values()
+            .map(|precedence| precedence.level)
+            .max()
+            .map_or(1, |level| level + 1);
+        for terminal in terminals.iter() {
+            self.terminals.insert(
+                *terminal,
+                Precedence {
+                    level,
+                    associativity,
+                },
+            );
+        }
+    }
+
+    ///The precedence of a production is the one given with %prec, otherwise
+    ///the one of the last terminal in its body
+    pub fn production<AST, Token, TranslatorStack>(
+        &self,
+        production: &Production<AST, Token, TranslatorStack>,
+        symbols: &Symbols,
+    ) -> Option<Precedence> {
+        let terminal = match self.productions.get(&ProductionId(production.index)) {
+            Some(terminal) => Some(terminal),
+            None => production
+                .body
+                .iter()
+                .rev()
+                .find(|symbol| symbols.terminal(symbol)),
+        };
+        terminal
+            .and_then(|terminal| self.terminals.get(terminal))
+            .copied()
+    }
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use logos::Span;
+
+use crate::value::SemanticValue;
+
+///Predicate of a production, decides at parse time whether the production
+///can be reduced, or its next terminal shifted
+pub type Predicate<AST, Token> = Arc<dyn Fn(&PredicateContext<AST, Token>) -> bool + Send + Sync>;
+
+///Wraps a closure as the predicate of a production, like semantic_action it
+///lets the compiler infer the type of the context
+pub fn semantic_predicate<AST, Token, F>(predicate: F) -> Predicate<AST, Token>
+where
+    F: Fn(&PredicateContext<AST, Token>) -> bool + Send + Sync + 'static,
+{
+    Arc::new(predicate)
+}
+
+///What a predicate can look at: the user state, the symbols of its production
+///parsed so far (the whole body when reducing) and the lookahead token
+pub struct PredicateContext<'a, AST, Token> {
+    pub(crate) ast: &'a AST,
+    pub(crate) spans: &'a [Span],
+    pub(crate) values: &'a [SemanticValue],
+    pub(crate) lookahead: &'a Token,
+}
+
+impl<AST, Token> PredicateContext<'_, AST, Token> {
+    pub fn ast(&self) -> &AST {
+        self.ast
+    }
+
+    ///span of every symbol parsed so far
+    pub fn spans(&self) -> &[Span] {
+        self.spans
+    }
+
+    ///semantic value of the symbol at `index`, None if it is not a `T`.
+    ///Terminals carry their token, nonterminals the value of their typed action.
+    pub fn value<T: 'static>(&self, index: usize) -> Option<&T> {
+        self.values.get(index)?.downcast_ref::<T>()
+    }
+
+    ///token the parser is about to shift, or the one following the reduced body
+    pub fn lookahead(&self) -> &Token {
+        self.lookahead
+    }
+}
@@ -1,15 +1,69 @@
-use std::{hash::Hash, rc::Rc};
+use std::{convert::Infallible, fmt::Display, hash::Hash, str::FromStr, sync::Arc};
 
 use indexmap::IndexMap;
-use logos::Span;
 use proc_macro2::TokenStream;
 
 use crate::{
-    error::ParseError,
+    context::ActionContext,
     interner::Interner,
+    predicate::Predicate,
     symbol::{SymbolId, AUGMENT_START_SYMBOL_ID, START_SYMBOL_ID},
+    value::{SemanticValue, ValueType},
 };
 
+///Semantic action of a production, shared so the parse tables can be used
+///from several threads at once
+pub type SemanticAction<AST, Token, TranslatorStack> =
+    Arc<dyn Fn(&mut ActionContext<AST, Token, TranslatorStack>) + Send + Sync>;
+
+///Typed action of a production, receives the semantic values of the body and
+///returns the head's value, Err when a value doesn't have the type the action
+///takes it as
+pub type ValueAction =
+    Arc<dyn Fn(Vec<SemanticValue>) -> Result<SemanticValue, String> + Send + Sync>;
+
+///Source of an action or predicate as written in the grammar, emitted again by
+///the code generator. It is kept as text since a `TokenStream` can't be sent
+///to another thread.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SourceTokens(String);
+
+impl SourceTokens {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn tokens(&self) -> TokenStream {
+        self.0.parse().expect("source tokens are always valid")
+    }
+}
+
+impl From<TokenStream> for SourceTokens {
+    fn from(tokens: TokenStream) -> Self {
+        SourceTokens(tokens.to_string())
+    }
+}
+
+impl FromStr for SourceTokens {
+    type Err = Infallible;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Ok(SourceTokens(source.to_string()))
+    }
+}
+
+impl Display for SourceTokens {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl quote::ToTokens for SourceTokens {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.extend(self.tokens());
+    }
+}
+
 ///A production is uniquely identified by its head,body,error_message,index
 #[derive(Clone)]
 pub struct Production<AST, Token, TranslatorStack> {
@@ -17,44 +71,63 @@ pub struct Production<AST, Token, TranslatorStack> {
     pub head: SymbolId,
     pub body: Vec<SymbolId>,
     pub error_message: Option<String>,
-    pub action_tokens: TokenStream,
-    pub action: Option<
-        Rc<
-            dyn Fn(
-                &mut AST,
-                &mut Vec<(Token, Span)>,
-                &mut Vec<TranslatorStack>,
-                &mut Vec<ParseError>,
-            ),
-        >,
-    >,
+    pub action_tokens: SourceTokens,
+    pub action: Option<SemanticAction<AST, Token, TranslatorStack>>,
+    pub value_action_tokens: SourceTokens,
+    ///typed action, receives the semantic values of the body and returns the head's value
+    pub value_action: Option<ValueAction>,
+    ///types the typed action takes the body's values as, checked against the
+    ///value types of the body symbols when the parser is built. None for
+    ///actions that take the values untyped.
+    pub argument_types: Option<Vec<ValueType>>,
+    ///type the typed action returns, checked against the value type of the
+    ///head when the parser is built
+    pub value_type: Option<ValueType>,
+    pub predicate_tokens: SourceTokens,
+    ///decides at parse time whether the production can be reduced or shifted into
+    pub predicate: Option<Predicate<AST, Token>>,
+}
+
+///Wraps a closure as the semantic action of a production, passing the closure
+///through an `Fn` bound lets the compiler infer the type of its context
+pub fn semantic_action<AST, Token, TranslatorStack, F>(
+    action: F,
+) -> SemanticAction<AST, Token, TranslatorStack>
+where
+    F: Fn(&mut ActionContext<AST, Token, TranslatorStack>) + Send + Sync + 'static,
+{
+    Arc::new(action)
 }
 
 impl<AST, Token, TranslatorStack> Production<AST, Token, TranslatorStack> {
+    #[allow(clippy::too_many_arguments)]
     pub fn n(
         index: usize,
         head: SymbolId,
         body: Vec<SymbolId>,
         error_message: Option<String>,
         action_tokens: TokenStream,
-        action: Option<
-            Rc<
-                dyn Fn(
-                    &mut AST,
-                    &mut Vec<(Token, Span)>,
-                    &mut Vec<TranslatorStack>,
-                    &mut Vec<ParseError>,
-                ),
-            >,
-        >,
+        action: Option<SemanticAction<AST, Token, TranslatorStack>>,
+        value_action_tokens: TokenStream,
+        value_action: Option<ValueAction>,
+        argument_types: Option<Vec<ValueType>>,
+        value_type: Option<ValueType>,
+        predicate_tokens: TokenStream,
+        predicate: Option<Predicate<AST, Token>>,
     ) -> Self {
         Production {
             index,
             head,
             body,
             error_message,
-            action_tokens,
+            action_tokens: action_tokens.into(),
             action,
+            value_action_tokens: value_action_tokens.into(),
+            value_action,
+            argument_types,
+            value_type,
+            predicate_tokens: predicate_tokens.into(),
+            predicate,
         }
     }
 }
@@ -76,6 +149,8 @@ impl<AST, Token, TranslatorStack> std::fmt::Debug for Production<AST, Token, Tra
             .field("error_message", &self.error_message)
             .field("index", &self.index)
             .field("action", &self.action_tokens.to_string())
+            .field("value_action", &self.value_action_tokens.to_string())
+            .field("predicate", &self.predicate_tokens.to_string())
             .finish()
     }
 }
@@ -133,9 +208,14 @@ where
             head: AUGMENT_START_SYMBOL_ID,
             body: vec![START_SYMBOL_ID],
             error_message: None,
-            #[allow(unused_variables)]
-            action: Some(Rc::new(|ast, token_stack, tl_stack, errors| {})),
-            action_tokens: quote::quote! {Some(Rc::new(|ast, token_stack, tl_stack, errors| {}))},
+            action: None,
+            action_tokens: quote::quote! {None}.into(),
+            value_action: None,
+            value_action_tokens: quote::quote! {None}.into(),
+            argument_types: None,
+            value_type: None,
+            predicate: None,
+            predicate_tokens: quote::quote! {None}.into(),
             index: 0,
         };
         productions.intern(augmented_production);
@@ -0,0 +1,177 @@
+use std::{collections::VecDeque, fmt::Debug};
+
+use indexmap::IndexSet;
+use logos::Span;
+
+use crate::{
+    action::Action,
+    error::Repair,
+    parser::LR1_Parser,
+    source::SourceToken,
+    state::StateId,
+    symbol::{SymbolId, EOF_SYMBOL_ID, ERROR_SYMBOL_ID},
+    token::TokenKind,
+};
+
+//Repairs of syntax errors in the style of CPCT+ (Diekmann and Tratt): the
+//cheapest sequences of token insertions, deletions and replacements after
+//which the parser can go on through the next tokens of the input are
+//searched breadth first, shifting an input token costs nothing and every
+//edit costs one. Only the state stack is simulated, no action runs. Repairs
+//are reported, and replace the input when LR1_Parser::repair_input is set.
+
+//Repair of the token at `position` in the repair window, an insertion
+//comes before it. `terminal` is the terminal inserted or replacing it.
+#[derive(Debug, Clone)]
+pub(crate) struct Edit {
+    pub position: usize,
+    pub terminal: Option<SymbolId>,
+    pub repair: Repair,
+}
+
+impl<AST, Token, TranslatorStack> LR1_Parser<AST, Token, TranslatorStack>
+where
+    AST: Clone + Debug + PartialEq + 'static,
+    Token: ToString + Debug + Clone + PartialEq + TokenKind + 'static,
+    TranslatorStack: Clone + Debug + PartialEq + 'static,
+{
+    //most edits a repair is made of
+    const REPAIR_COST: usize = 3;
+    //tokens from the error on a repair has to let the parser shift
+    const REPAIR_WINDOW: usize = 4;
+
+    ///The error token and the tokens following it, up to REPAIR_WINDOW of
+    ///them, looked ahead with `peek`. The window ends at the end of the
+    ///input when fewer tokens are left.
+    pub(crate) fn repair_window(
+        &self,
+        symbol: SymbolId,
+        span: Span,
+        peek: impl FnOnce(usize) -> Vec<SourceToken<Token>>,
+        end: usize,
+    ) -> Vec<(SymbolId, Span)> {
+        let mut window = vec![(symbol, span)];
+        if symbol == EOF_SYMBOL_ID {
+            return window;
+        }
+        let peeked = peek(Self::REPAIR_WINDOW - 1);
+        let ended = peeked.len() < Self::REPAIR_WINDOW - 1;
+        //lexer errors and tokens the grammar never uses can only be
+        //deleted or replaced
+        window.extend(peeked.into_iter().map(|input| {
+            let (symbol, _, span) = self.input_symbol(input);
+            (symbol, span)
+        }));
+        if ended {
+            window.push((EOF_SYMBOL_ID, end..end));
+        }
+        window
+    }
+
+    ///Cheapest edits of `window` after which its last token can be shifted
+    ///(or accepted at the end of the input), empty if there are none within
+    ///REPAIR_COST edits
+    pub(crate) fn repair(&self, stack: &[StateId], window: &[(SymbolId, Span)]) -> Vec<Edit> {
+        let terminals: Vec<SymbolId> = self
+            .grammar
+            .symbols
+            .terminals
+            .iter()
+            .copied()
+            .filter(|terminal| ![EOF_SYMBOL_ID, ERROR_SYMBOL_ID].contains(terminal))
+            .collect();
+        let last = window.len() - 1;
+
+        //0-1 breadth first search, shifts are pushed in front of the edits
+        let mut pending: VecDeque<(Vec<StateId>, usize, Vec<Edit>)> =
+            VecDeque::from([(stack.to_vec(), 0, vec![])]);
+        let mut visited: IndexSet<(Vec<StateId>, usize)> = IndexSet::new();
+        while let Some((stack, position, edits)) = pending.pop_front() {
+            if !visited.insert((stack.clone(), position)) {
+                continue;
+            }
+            let (symbol, span) = &window[position];
+            if let Some(shifted) = self.simulate(&stack, *symbol) {
+                if position == last {
+                    return edits;
+                }
+                pending.push_front((shifted, position + 1, edits.clone()));
+            }
+            if edits.len() == Self::REPAIR_COST {
+                continue;
+            }
+            let edited = |terminal: Option<SymbolId>, repair: Repair| {
+                let mut edits = edits.clone();
+                edits.push(Edit {
+                    position,
+                    terminal,
+                    repair,
+                });
+                edits
+            };
+            //the last token is the one proving the repair, it is kept.
+            //Deletions come first as they need no made up token.
+            if position < last {
+                let delete = Repair::Delete { span: span.clone() };
+                pending.push_back((stack.clone(), position + 1, edited(None, delete)));
+            }
+            for terminal in terminals.iter() {
+                if let Some(shifted) = self.simulate(&stack, *terminal) {
+                    let insert = Repair::Insert {
+                        terminal: self.terminal_display_name(*terminal),
+                        at: span.start,
+                    };
+                    pending.push_back((shifted, position, edited(Some(*terminal), insert)));
+                }
+            }
+            if position == last {
+                continue;
+            }
+            for terminal in terminals.iter().filter(|terminal| *terminal != symbol) {
+                if let Some(shifted) = self.simulate(&stack, *terminal) {
+                    let replace = Repair::Replace {
+                        span: span.clone(),
+                        terminal: self.terminal_display_name(*terminal),
+                    };
+                    pending.push_back((shifted, position + 1, edited(Some(*terminal), replace)));
+                }
+            }
+        }
+        vec![]
+    }
+
+    //token of `terminal` inserted by an applied repair, see
+    //TokenKind::repair_token
+    pub(crate) fn repair_token(&self, terminal: SymbolId) -> Option<Token> {
+        let terminal_id = self
+            .terminal_table
+            .iter()
+            .position(|symbol| *symbol == Some(terminal))?;
+        Token::repair_token(terminal_id)
+    }
+
+    //State stack after the reductions `symbol` triggers and its shift, None
+    //on a syntax error. The stack is returned unchanged when EOF is accepted.
+    pub(crate) fn simulate(&self, stack: &[StateId], symbol: SymbolId) -> Option<Vec<StateId>> {
+        if symbol == ERROR_SYMBOL_ID {
+            return None;
+        }
+        let mut stack = stack.to_vec();
+        loop {
+            match self.action.get(stack.last()?)?.get(&symbol)? {
+                Action::SHIFT(state) => {
+                    stack.push(*state);
+                    return Some(stack);
+                }
+                Action::REDUCE(production) => {
+                    let production = &self.grammar.productions.vec[production.0];
+                    stack.truncate(stack.len().checked_sub(production.body.len())?);
+                    let goto = self.goto.get(stack.last()?)?.get(&production.head)?;
+                    stack.push(*goto);
+                }
+                Action::ACCEPT => return Some(stack),
+                Action::ERROR(_) => return None,
+            }
+        }
+    }
+}
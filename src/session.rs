@@ -0,0 +1,714 @@
+use std::{collections::VecDeque, fmt::Debug};
+
+use logos::Span;
+
+use crate::{
+    action::Action,
+    context::{ActionContext, LexerControl},
+    error::{ParseError, Repair},
+    interner::Interner,
+    outcome::{InputStatus, ParseFailure, ParseOutcome, ParseStats, PushStatus},
+    parser::LR1_Parser,
+    predicate::PredicateContext,
+    production::ProductionId,
+    repair::Edit,
+    source::{SourceToken, TokenSource},
+    state::StateId,
+    symbol::{SymbolId, EOF_SYMBOL_ID, ERROR_SYMBOL_ID},
+    token::{ProductionTokens, TokenKind},
+    value::SemanticValue,
+};
+
+///Mutable state of one parse: the parse stacks, the lookahead and what the
+///recovery has done so far. The parser itself is only borrowed, so a single
+///parser can run any number of sessions at once, from several threads.
+///
+///`LR1_Parser::parse` runs a session over a token source, `LR1_Parser::session`
+///starts one that is pushed tokens one at a time:
+///
+///```ignore
+///let mut session = parser.session(&mut errors, &mut ast);
+///for (token, span) in tokens {
+///    session.push(token, span)?;
+///}
+///let outcome = session.finish()?;
+///```
+pub struct ParseSession<'s, AST, Token, TranslatorStack> {
+    parser: &'s LR1_Parser<AST, Token, TranslatorStack>,
+    errors: &'s mut Vec<ParseError>,
+    ast: &'s mut AST,
+    //None when the tokens are pushed
+    source: Option<&'s mut dyn TokenSource<Token>>,
+    current_input: Token,
+    current_input_symbol: SymbolId,
+    current_span: Span,
+    //text of the lookahead when it is a lexical error
+    current_slice: Option<String>,
+    //tokens read from the source past the lookahead, to search repairs on,
+    //they are the next lookaheads. None is the end of the input.
+    peeked: VecDeque<SourceInput<Token>>,
+    stack: Vec<StateId>,
+    translator_stack: Vec<TranslatorStack>,
+    input_token_stack: Vec<(Token, Span)>,
+    value_stack: Vec<SemanticValue>,
+    span_stack: Vec<Span>,
+    //whether the grammar has predicates, they need the stacks
+    predicates: bool,
+    //an action reached the lexer extras
+    extras_reached: bool,
+    //an action lexed the lookahead again, it has to be read
+    relexed: bool,
+    //tokens shifted since the last error recovery
+    shifted_since_recovery: usize,
+    //an input that is only the prefix of a valid one ends the parse
+    //without error instead of being recovered
+    stop_when_incomplete: bool,
+    //input is being discarded until a token the state on top of the stack
+    //can act on, within the FOLLOW set of the nonterminal in panic mode
+    discarding: Option<Option<SymbolId>>,
+    stats: ParseStats,
+    //the parser gave up, every later push and finish returns it
+    failure: Option<ParseFailure>,
+}
+
+//value of a nonterminal recovered by panic mode or whose typed action
+//failed, the values it is part of are incomplete
+struct Poisoned;
+
+//token read from a source with its text when it is a lexical error, None
+//at the end of the input
+type SourceInput<Token> = (Option<SourceToken<Token>>, Option<String>);
+
+//what became of the lookahead
+enum Step {
+    Shifted,
+    Discarded,
+    Accepted,
+    //the input ended where more was expected, see stop_when_incomplete
+    Incomplete,
+}
+
+impl<'s, AST, Token, TranslatorStack> ParseSession<'s, AST, Token, TranslatorStack>
+where
+    AST: Clone + Debug + PartialEq + 'static,
+    Token: ToString + Debug + Clone + PartialEq + TokenKind + 'static,
+    TranslatorStack: Clone + Debug + PartialEq + 'static,
+{
+    //number of tokens to shift after an error recovery before errors are no
+    //longer cascaded
+    const RECOVERY_SHIFTS: usize = 3;
+
+    ///Session pushed tokens one at a time, see LR1_Parser::session
+    pub fn new(
+        parser: &'s LR1_Parser<AST, Token, TranslatorStack>,
+        errors: &'s mut Vec<ParseError>,
+        ast: &'s mut AST,
+    ) -> Self {
+        let S0 = *parser.LR1_automata.map.first().unwrap().1;
+        ParseSession {
+            parser,
+            errors,
+            ast,
+            source: None,
+            current_input: Token::eof().into(),
+            current_input_symbol: EOF_SYMBOL_ID,
+            current_span: 0..0,
+            current_slice: None,
+            peeked: VecDeque::new(),
+            stack: vec![S0],
+            translator_stack: Vec::new(),
+            input_token_stack: Vec::new(),
+            value_stack: Vec::new(),
+            span_stack: Vec::new(),
+            predicates: parser
+                .grammar
+                .productions
+                .vec
+                .iter()
+                .any(|production| production.predicate.is_some()),
+            extras_reached: false,
+            relexed: false,
+            shifted_since_recovery: Self::RECOVERY_SHIFTS,
+            stop_when_incomplete: false,
+            discarding: None,
+            stats: ParseStats::default(),
+            failure: None,
+        }
+    }
+
+    //session reading `source`, positioned on its first token
+    pub(crate) fn with_source(
+        parser: &'s LR1_Parser<AST, Token, TranslatorStack>,
+        source: &'s mut dyn TokenSource<Token>,
+        errors: &'s mut Vec<ParseError>,
+        ast: &'s mut AST,
+    ) -> Self {
+        let mut session = Self::new(parser, errors, ast);
+        session.source = Some(source);
+        session.advance();
+        session
+    }
+
+    //runs the parse to the end of the source, see LR1_Parser::parse
+    pub(crate) fn run(mut self) -> Result<ParseOutcome, ParseFailure> {
+        self.run_to_end()?;
+        Ok(self.outcome())
+    }
+
+    //runs the parse to the end of the source, telling apart input that is
+    //incomplete, see LR1_Parser::parse_interactive
+    pub(crate) fn run_interactive(mut self) -> InputStatus {
+        self.stop_when_incomplete = true;
+        match self.run_to_end() {
+            Ok(Step::Incomplete) => InputStatus::Incomplete(self.stats),
+            Ok(_) if self.stats.recoveries == 0 => InputStatus::Accepted(self.outcome()),
+            Ok(_) => InputStatus::Invalid(self.stats),
+            Err(failure) => InputStatus::Invalid(failure.stats),
+        }
+    }
+
+    //Accepted or Incomplete
+    fn run_to_end(&mut self) -> Result<Step, ParseFailure> {
+        loop {
+            match self.step()? {
+                Step::Shifted | Step::Discarded => self.advance(),
+                step => return Ok(step),
+            }
+        }
+    }
+
+    ///Feeds the next token of the input, the actions of the productions it
+    ///completes run before it is shifted. Err when the parser gave up, the
+    ///later calls to push and finish return the same failure then. The end of
+    ///the input is told with finish, not with the EOF token.
+    pub fn push(&mut self, token: Token, span: Span) -> Result<PushStatus, ParseFailure> {
+        if let Some(failure) = &self.failure {
+            return Err(failure.clone());
+        }
+        let status = self.push_token(token, span);
+        if let Err(failure) = &status {
+            self.failure = Some(failure.clone());
+        }
+        status
+    }
+
+    fn push_token(&mut self, token: Token, span: Span) -> Result<PushStatus, ParseFailure> {
+        let recoveries = self.stats.recoveries;
+        self.read(Some(Ok((token, span))));
+        let mut step = self.step()?;
+        //tokens of an applied repair
+        while !self.peeked.is_empty() {
+            self.advance();
+            step = self.step()?;
+        }
+        Ok(match step {
+            _ if self.stats.recoveries > recoveries => PushStatus::Error,
+            Step::Shifted => {
+                //accepting the end of the input takes no shift
+                match self.parser.simulate(&self.stack, EOF_SYMBOL_ID) {
+                    Some(_) => PushStatus::Complete,
+                    None => PushStatus::Incomplete,
+                }
+            }
+            _ => PushStatus::Recovering,
+        })
+    }
+
+    ///Ends the input, the remaining productions are reduced and Start is
+    ///accepted as by LR1_Parser::parse
+    pub fn finish(mut self) -> Result<ParseOutcome, ParseFailure> {
+        if let Some(failure) = self.failure {
+            return Err(failure);
+        }
+        self.read(None);
+        loop {
+            //the end of the input is never shifted nor discarded, the tokens
+            //an applied repair inserts before it are
+            match self.step()? {
+                Step::Accepted => return Ok(self.outcome()),
+                _ => self.advance(),
+            }
+        }
+    }
+
+    ///What the parser did so far
+    pub fn stats(&self) -> ParseStats {
+        self.stats
+    }
+
+    //Acts on the lookahead until it is shifted, discarded by a recovery or
+    //accepted, Err when the recovery reached the end of the input
+    fn step(&mut self) -> Result<Step, ParseFailure> {
+        let parser = self.parser;
+        //told before the end of the input reduces anything, the actions
+        //don't see an input that goes on
+        if self.stop_when_incomplete
+            && self.current_input_symbol == EOF_SYMBOL_ID
+            && self.stats.recoveries == 0
+            && parser.simulate(&self.stack, EOF_SYMBOL_ID).is_none()
+            && self.continues()
+        {
+            return Ok(Step::Incomplete);
+        }
+        loop {
+            let symbol_id = self.current_input_symbol;
+            if let Some(follow) = self.discarding {
+                let state = self.stack.last().unwrap();
+                let acts = symbol_id != ERROR_SYMBOL_ID
+                    && parser.action[state].contains_key(&symbol_id)
+                    && follow.is_none_or(|nonterminal| {
+                        parser.follow_set[&nonterminal].contains(&symbol_id)
+                    });
+                if !acts {
+                    if symbol_id == EOF_SYMBOL_ID {
+                        return Err(self.failure());
+                    }
+                    //the skipped token joins the recovered symbol
+                    let end = self.current_span.end;
+                    self.span_stack.last_mut().unwrap().end = end;
+                    if follow.is_none() {
+                        self.input_token_stack.last_mut().unwrap().1.end = end;
+                    }
+                    self.stats.discarded += 1;
+                    return Ok(Step::Discarded);
+                }
+                self.discarding = None;
+            }
+
+            let S0 = *self.stack.last().unwrap();
+            //every state will be in action_map so unwrap
+            let action_map = parser.action.get(&S0).unwrap();
+            //ERROR entries come from non associative operators, they are syntax
+            //errors, so is the error variant of the lexer
+            let action = action_map.get(&symbol_id).filter(|action| {
+                symbol_id != ERROR_SYMBOL_ID && !matches!(action, Action::ERROR(_))
+            });
+            let action = match action {
+                Some(action) if !self.predicates => Some(action.clone()),
+                Some(action) => parser.predicated_action(
+                    S0,
+                    symbol_id,
+                    action,
+                    &PredicateContext {
+                        ast: &*self.ast,
+                        spans: &self.span_stack,
+                        values: &self.value_stack,
+                        lookahead: &self.current_input,
+                    },
+                ),
+                None => None,
+            };
+            match action {
+                Some(Action::SHIFT(stateId)) => {
+                    self.stack.push(stateId);
+                    self.shifted_since_recovery += 1;
+                    self.stats.tokens += 1;
+
+                    //To maintain current input as a stack helps library user;
+                    self.input_token_stack
+                        .push((self.current_input.clone(), self.current_span.clone()));
+                    self.value_stack.push(Box::new(self.current_input.clone()));
+                    self.span_stack.push(self.current_span.clone());
+                    return Ok(Step::Shifted);
+                }
+                Some(Action::REDUCE(productionId)) => self.reduce(S0, productionId),
+                Some(Action::ACCEPT) => return Ok(Step::Accepted),
+                Some(Action::ERROR(_)) | None => {
+                    //predicates the simulation doesn't run may still turn
+                    //the end of the input into an error
+                    if self.stop_when_incomplete
+                        && symbol_id == EOF_SYMBOL_ID
+                        && self.stats.recoveries == 0
+                        && self.continues()
+                    {
+                        return Ok(Step::Incomplete);
+                    }
+                    if self.recover(symbol_id)? {
+                        return Ok(Step::Discarded);
+                    }
+                }
+            }
+        }
+    }
+
+    fn reduce(&mut self, state: StateId, productionId: ProductionId) {
+        let parser = self.parser;
+        self.stats.reductions += 1;
+        let production = parser.grammar.productions.lookup(productionId);
+        let body_len = production.body_len();
+        let terminals_count = production
+            .body
+            .iter()
+            .filter(|symbol| parser.grammar.symbols.terminal(symbol))
+            .count();
+        let tokens = self
+            .input_token_stack
+            .split_off(self.input_token_stack.len() - terminals_count);
+        let spans = self.span_stack.split_off(self.span_stack.len() - body_len);
+        let span = match (spans.first(), spans.last()) {
+            (Some(first), Some(last)) => first.start..last.end,
+            //empty production is located at the end of the previous symbol
+            _ => {
+                let end = self.span_stack.last().map_or(0, |span| span.end);
+                end..end
+            }
+        };
+        //a mid-rule action sees the symbols to its left, they
+        //stay on the stacks for the enclosing production
+        let (tokens, spans, action_span) = match parser.grammar.mid_rules.get(&production.head) {
+            Some((left, terminals)) => {
+                let tokens =
+                    self.input_token_stack[self.input_token_stack.len() - terminals..].to_vec();
+                let spans = self.span_stack[self.span_stack.len() - left..].to_vec();
+                let action_span = match spans.first() {
+                    Some(first) => first.start..span.end,
+                    None => span.clone(),
+                };
+                (tokens, spans, action_span)
+            }
+            None => (tokens, spans, span.clone()),
+        };
+        if let Some(action) = &production.action {
+            let production_tokens = ProductionTokens {
+                tokens: &tokens,
+                spans: &spans,
+                span: action_span,
+            };
+            let source: Option<&mut dyn TokenSource<Token>> = match &mut self.source {
+                Some(source) => Some(&mut **source),
+                None => None,
+            };
+            let mut context = ActionContext::new(
+                self.ast,
+                production_tokens,
+                &mut self.translator_stack,
+                self.errors,
+                productionId,
+                state,
+                LexerControl::new(
+                    source,
+                    self.current_span.start,
+                    &mut self.extras_reached,
+                    &mut self.relexed,
+                ),
+            );
+            (action.as_ref())(&mut context);
+        }
+        if self.relexed {
+            //the source was restarted at the lookahead's start, the tokens
+            //after it are read again as well
+            self.peeked.clear();
+            self.advance();
+        } else if self.extras_reached {
+            //tokens looked ahead were lexed before the extras changed
+            let start = match self.peeked.front() {
+                Some((Some(Ok((_, span)) | Err(span)), _)) => Some(span.start),
+                _ => None,
+            };
+            if let (Some(start), Some(source)) = (start, &mut self.source) {
+                if source.relex(start) {
+                    self.peeked.clear();
+                }
+            }
+        }
+        self.relexed = false;
+        self.extras_reached = false;
+        self.span_stack.push(span.clone());
+        let values = self
+            .value_stack
+            .split_off(self.value_stack.len() - body_len);
+        //nonterminals recovered by panic mode have no value, neither do the
+        //ones they are reduced into: their typed actions are skipped
+        let poisoned = values.iter().any(|value| value.is::<Poisoned>());
+        let value: SemanticValue = match &production.value_action {
+            _ if poisoned => Box::new(Poisoned),
+            Some(value_action) => match (value_action.as_ref())(values) {
+                Ok(value) => value,
+                Err(message) => {
+                    self.errors.push(ParseError::new(span, message));
+                    Box::new(Poisoned)
+                }
+            },
+            None => Box::new(()),
+        };
+        self.value_stack.push(value);
+        self.stack.truncate(self.stack.len() - body_len);
+        let stack_top = self.stack.last().unwrap();
+        let goto_map = parser.goto.get(stack_top).unwrap();
+        let goto_stack = goto_map.get(&production.head);
+        if let Some(goto_stack) = goto_stack {
+            self.stack.push(*goto_stack);
+        }
+    }
+
+    //Recovers from a syntax error on `symbol_id`. Error productions pop
+    //states until one shifts the error terminal, which is shifted with the
+    //token the error was detected on, panic mode pops states until one has a
+    //goto on a recovery nonterminal and pushes it. Input is then discarded
+    //until a token the parser can act on. Returns whether the lookahead was
+    //discarded, Err when the end of the input can't be recovered from.
+    fn recover(&mut self, symbol_id: SymbolId) -> Result<bool, ParseFailure> {
+        let parser = self.parser;
+        self.stats.recoveries += 1;
+        //a repair the parser erred on again right away is not applied twice
+        let apply = parser.repair_input && self.shifted_since_recovery > 0;
+        let span = self.current_span.clone();
+        let end = self
+            .source
+            .as_ref()
+            .map(|source| source.end().unwrap_or(span.end));
+        let window = match end {
+            Some(end) => parser.repair_window(symbol_id, span, |count| self.peek(count), end),
+            //no token after the error is known yet
+            None => vec![(symbol_id, span)],
+        };
+        let edits = parser.repair(&self.stack, &window);
+        let slice = self.current_slice.clone();
+        let repair = edits.iter().map(|edit| edit.repair.clone()).collect();
+        let mut error =
+            parser.syntax_error(&self.stack, &self.current_input, slice, &window, repair);
+        //errors that follow a recovery too closely are usually caused
+        //by the recovery itself
+        error.cascaded = self.shifted_since_recovery < Self::RECOVERY_SHIFTS;
+        self.errors.push(error);
+        if apply && !edits.is_empty() && self.apply_repair(window.len(), &edits) {
+            self.shifted_since_recovery = 0;
+            return Ok(false);
+        }
+        let error_token = self.current_input.clone();
+        //erroring again on the token a recovery stopped at, skip it so
+        //recovery always makes progress
+        let mut skipped: Option<Span> = None;
+        if self.shifted_since_recovery == 0 {
+            //the end of the input can't be skipped
+            if symbol_id == EOF_SYMBOL_ID {
+                return Err(self.failure());
+            }
+            skipped = Some(self.current_span.clone());
+            self.stats.discarded += 1;
+        }
+        self.shifted_since_recovery = 0;
+
+        //error productions: the error terminal stands for the popped symbols
+        //and the discarded tokens
+        if let Some((depth, error_state)) = parser.error_shift(&self.stack) {
+            let popped = self.pop_states(depth);
+            let start = popped
+                .first()
+                .or(skipped.as_ref())
+                .map_or(self.current_span.start, |span| span.start);
+            let end = skipped
+                .clone()
+                .or(popped.last().cloned())
+                .map_or(start, |span| span.end);
+            self.stack.push(error_state);
+            self.input_token_stack
+                .push((error_token.clone(), start..end));
+            self.value_stack.push(Box::new(error_token));
+            self.span_stack.push(start..end);
+            self.discarding = Some(None);
+            return Ok(skipped.is_some());
+        }
+        //panic mode
+        let Some((depth, nonterminal)) = parser.recovery_point(&self.stack) else {
+            //nothing to recover with, drop the token
+            if symbol_id == EOF_SYMBOL_ID {
+                return Err(self.failure());
+            }
+            if skipped.is_none() {
+                self.stats.discarded += 1;
+            }
+            return Ok(true);
+        };
+        let goto_state = parser.goto[&self.stack[depth - 1]][&nonterminal];
+
+        //the popped symbols and the skipped tokens become the nonterminal
+        let popped = self.pop_states(depth);
+        let start = popped
+            .first()
+            .or(skipped.as_ref())
+            .map_or(self.current_span.start, |span| span.start);
+        let end = skipped
+            .clone()
+            .or(popped.last().cloned())
+            .map_or(start, |span| span.end);
+        self.stack.push(goto_state);
+        self.span_stack.push(start..end);
+        self.value_stack.push(Box::new(Poisoned));
+        self.discarding = Some(Some(nonterminal));
+        Ok(skipped.is_some())
+    }
+
+    //Replaces the lookahead and the `window_len - 1` tokens after it with
+    //their repair, the first token of the repaired input becomes the
+    //lookahead. Returns false, leaving the input alone, when an inserted
+    //terminal has no token.
+    fn apply_repair(&mut self, window_len: usize, edits: &[Edit]) -> bool {
+        let parser = self.parser;
+        let Some(tokens) = edits
+            .iter()
+            .map(|edit| match edit.terminal {
+                Some(terminal) => parser.repair_token(terminal).map(Some),
+                None => Some(None),
+            })
+            .collect::<Option<Vec<Option<Token>>>>()
+        else {
+            return false;
+        };
+        let current = match self.current_input_symbol {
+            EOF_SYMBOL_ID => None,
+            _ => Some(Ok((self.current_input.clone(), self.current_span.clone()))),
+        };
+        let mut window = vec![(current, self.current_slice.take())];
+        //the window ends at the end of the input when it has fewer tokens
+        for _ in 1..window_len {
+            window.push(self.peeked.pop_front().unwrap_or((None, None)));
+        }
+        let mut edits = edits.iter().zip(tokens).peekable();
+        let mut repaired: Vec<SourceInput<Token>> = vec![];
+        for (position, input) in window.into_iter().enumerate() {
+            let mut kept = Some(input);
+            while let Some((edit, token)) = edits.next_if(|(edit, _)| edit.position == position) {
+                let span = match &edit.repair {
+                    Repair::Insert { at, .. } => *at..*at,
+                    Repair::Delete { span } => {
+                        self.stats.discarded += 1;
+                        kept = None;
+                        span.clone()
+                    }
+                    Repair::Replace { span, .. } => {
+                        kept = None;
+                        span.clone()
+                    }
+                };
+                if let Some(token) = token {
+                    repaired.push((Some(Ok((token, span))), None));
+                }
+            }
+            repaired.extend(kept);
+        }
+        for input in repaired.into_iter().rev() {
+            self.peeked.push_front(input);
+        }
+        self.advance();
+        true
+    }
+
+    //whether a terminal other than the end of the input can follow the
+    //input read so far
+    fn continues(&self) -> bool {
+        self.parser
+            .grammar
+            .symbols
+            .terminals
+            .iter()
+            .filter(|terminal| ![EOF_SYMBOL_ID, ERROR_SYMBOL_ID].contains(terminal))
+            .any(|terminal| self.parser.simulate(&self.stack, *terminal).is_some())
+    }
+
+    //reads the next token of the source as the lookahead
+    fn advance(&mut self) {
+        let (input, slice) = match self.peeked.pop_front() {
+            Some(input) => input,
+            None => match &mut self.source {
+                Some(source) => Self::read_source(self.parser, &mut **source),
+                None => return,
+            },
+        };
+        self.read(input);
+        self.current_slice = slice;
+    }
+
+    //up to `count` tokens following the lookahead, fewer when the input ends
+    //before. They are read from the source and kept until the parser gets
+    //to them, so the source is never read twice.
+    fn peek(&mut self, count: usize) -> Vec<SourceToken<Token>> {
+        if let Some(source) = &mut self.source {
+            while self.peeked.len() < count && !matches!(self.peeked.back(), Some((None, _))) {
+                self.peeked
+                    .push_back(Self::read_source(self.parser, &mut **source));
+            }
+        }
+        self.peeked
+            .iter()
+            .take(count)
+            .map_while(|(input, _)| input.clone())
+            .collect()
+    }
+
+    //the text is only kept for lexer errors, the error variant included
+    fn read_source(
+        parser: &LR1_Parser<AST, Token, TranslatorStack>,
+        source: &mut dyn TokenSource<Token>,
+    ) -> SourceInput<Token> {
+        let input = source.next_token();
+        let slice = match &input {
+            Some(Err(_)) => source.slice(),
+            Some(Ok((token, _))) if parser.terminal_symbol(token) == Some(ERROR_SYMBOL_ID) => {
+                source.slice()
+            }
+            _ => None,
+        };
+        (input, slice)
+    }
+
+    //makes `input` the lookahead, None is the end of the input
+    fn read(&mut self, input: Option<SourceToken<Token>>) {
+        self.current_slice = None;
+        match input {
+            Some(input) => {
+                let (symbol, token, span) = self.parser.input_symbol(input);
+                self.current_input_symbol = symbol;
+                self.current_input = token;
+                self.current_span = span;
+            }
+            None => {
+                let end = self
+                    .source
+                    .as_ref()
+                    .and_then(|source| source.end())
+                    .or(self.span_stack.last().map(|span| span.end))
+                    .unwrap_or(self.current_span.end);
+                self.current_input = Token::eof().into();
+                self.current_input_symbol = EOF_SYMBOL_ID;
+                self.current_span = end..end;
+            }
+        }
+    }
+
+    fn outcome(&mut self) -> ParseOutcome {
+        ParseOutcome {
+            value: self
+                .value_stack
+                .pop()
+                .filter(|value| !value.is::<Poisoned>()),
+            recovered: self.stats.recoveries > 0,
+            stats: self.stats,
+        }
+    }
+
+    fn failure(&self) -> ParseFailure {
+        ParseFailure {
+            span: self.current_span.clone(),
+            stats: self.stats,
+        }
+    }
+
+    //Truncates the parse stacks to the first `depth` states, returns the spans
+    //of the popped symbols
+    fn pop_states(&mut self, depth: usize) -> Vec<Span> {
+        let parser = self.parser;
+        let terminals = self.stack[depth..]
+            .iter()
+            .filter(|state| {
+                let state = &parser.LR1_automata.vec[state.0];
+                parser.grammar.symbols.terminal(&state.transition_symbol)
+            })
+            .count();
+        self.stack.truncate(depth);
+        self.input_token_stack
+            .truncate(self.input_token_stack.len() - terminals);
+        self.value_stack.truncate(depth - 1);
+        self.span_stack.split_off(depth - 1)
+    }
+}
@@ -0,0 +1,129 @@
+use std::any::Any;
+
+use logos::{Lexer, Logos, Source, Span};
+
+///Token read from a TokenSource with its span, Err with the span of input
+///that is not a token
+pub type SourceToken<Token> = Result<(Token, Span), Span>;
+
+///Where the parser reads its tokens from: a logos lexer, a hand-written
+///lexer, or tokens lexed beforehand. Only `next_token` is required, the other
+///methods let the parser do more when the source supports it. The parser may
+///read a few tokens past its lookahead to search repairs of syntax errors.
+pub trait TokenSource<Token> {
+    ///next token of the input, None at its end
+    fn next_token(&mut self) -> Option<SourceToken<Token>>;
+
+    ///text of the last token read, shown in lexical errors
+    fn slice(&self) -> Option<String> {
+        None
+    }
+
+    ///position of the end of the input, where it is reported
+    fn end(&self) -> Option<usize> {
+        None
+    }
+
+    ///state of the lexer reachable by actions through ActionContext::extras
+    fn extras(&mut self) -> Option<&mut dyn Any> {
+        None
+    }
+
+    ///lexes the input again from `start`, the start of a token already read,
+    ///with the current extras: the next call to next_token returns the token
+    ///at `start`. False if the source can't.
+    fn relex(&mut self, _start: usize) -> bool {
+        false
+    }
+}
+
+impl<'a, Token> TokenSource<Token> for Lexer<'a, Token>
+where
+    Token: Logos<'a> + Clone,
+    Token::Extras: Default + 'static,
+{
+    fn next_token(&mut self) -> Option<SourceToken<Token>> {
+        match self.next()? {
+            Ok(token) => Some(Ok((token, self.span()))),
+            Err(_) => Some(Err(self.span())),
+        }
+    }
+
+    fn slice(&self) -> Option<String> {
+        //the source may not be text, its slices are only Debug
+        Some(format!("{:?}", Lexer::slice(self)))
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.source().len())
+    }
+
+    fn extras(&mut self) -> Option<&mut dyn Any> {
+        Some(&mut self.extras)
+    }
+
+    fn relex(&mut self, start: usize) -> bool {
+        //logos lexers only go forward, a new one takes over the extras
+        let extras = std::mem::take(&mut self.extras);
+        let mut lexer = Lexer::with_extras(self.source(), extras);
+        lexer.bump(start);
+        *self = lexer;
+        true
+    }
+}
+
+///Tokens lexed beforehand, ex: `tokens.into_iter()` of a
+///`Vec<(Token, Span)>`
+impl<Token> TokenSource<Token> for std::vec::IntoIter<(Token, Span)> {
+    fn next_token(&mut self) -> Option<SourceToken<Token>> {
+        self.next().map(Ok)
+    }
+}
+
+///Tokens lexed beforehand with the lexical errors among them, ex:
+///`tokens.into_iter()` of a `Vec<SourceToken<Token>>`
+impl<Token> TokenSource<Token> for std::vec::IntoIter<SourceToken<Token>> {
+    fn next_token(&mut self) -> Option<SourceToken<Token>> {
+        self.next()
+    }
+}
+
+///Any iterator of tokens and their spans, from a hand-written lexer or
+///another crate
+#[derive(Debug, Clone)]
+pub struct TokenIter<I>(pub I);
+
+impl<Token, I> TokenSource<Token> for TokenIter<I>
+where
+    I: Iterator<Item = (Token, Span)>,
+{
+    fn next_token(&mut self) -> Option<SourceToken<Token>> {
+        self.0.next().map(Ok)
+    }
+}
+
+impl<T> From<Vec<T>> for TokenIter<std::vec::IntoIter<T>> {
+    fn from(tokens: Vec<T>) -> Self {
+        TokenIter(tokens.into_iter())
+    }
+}
+
+///Any iterator of tokens or lexical errors, ex: a hand-written lexer
+///implementing `Iterator<Item = SourceToken<Token>>`
+#[derive(Debug, Clone)]
+pub struct TokenResults<I>(pub I);
+
+impl<Token, I> TokenSource<Token> for TokenResults<I>
+where
+    I: Iterator<Item = SourceToken<Token>>,
+{
+    fn next_token(&mut self) -> Option<SourceToken<Token>> {
+        self.0.next()
+    }
+}
+
+impl<T> From<Vec<T>> for TokenResults<std::vec::IntoIter<T>> {
+    fn from(tokens: Vec<T>) -> Self {
+        TokenResults(tokens.into_iter())
+    }
+}
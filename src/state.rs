@@ -2,7 +2,6 @@ use std::{
     cell::RefCell,
     fmt::Debug,
     hash::{self, Hash},
-    ops::Deref,
     rc::Rc,
 };
 
@@ -20,8 +19,15 @@ pub struct State {
     pub index: usize,
     pub items: Vec<Item>,
     pub transition_symbol: SymbolId,
-    pub outgoing: IndexMap<SymbolId, Rc<RefCell<State>>>,
-    pub incoming: Vec<Rc<RefCell<State>>>,
+}
+
+//State of the automaton being built with its transitions, the parser keeps
+//only the State so it can be shared between threads
+#[derive(Debug, Clone)]
+pub struct StateNode {
+    pub state: State,
+    pub outgoing: IndexMap<SymbolId, Rc<RefCell<StateNode>>>,
+    pub incoming: Vec<Rc<RefCell<StateNode>>>,
 }
 
 //Uniquely identifies a state by its item.cursor and item.production fields
@@ -48,14 +54,22 @@ impl PartialEq for State {
     }
 }
 
+impl StateNode {
+    pub fn new(state: State) -> StateNode {
+        StateNode {
+            state,
+            outgoing: IndexMap::new(),
+            incoming: vec![],
+        }
+    }
+}
+
 impl State {
     pub fn new(index: usize, items: Vec<Item>, transition_symbol: SymbolId) -> State {
         State {
             index,
             items,
             transition_symbol,
-            outgoing: IndexMap::new(),
-            incoming: vec![],
         }
     }
 
@@ -95,62 +109,69 @@ pub trait StateVecExtension<T> {
     fn custom_contains(&self, other: &Rc<RefCell<T>>) -> bool;
 }
 
-impl StateVecExtension<State> for Vec<Rc<RefCell<State>>> {
+impl StateVecExtension<StateNode> for Vec<Rc<RefCell<StateNode>>> {
     fn merge_sets(&mut self) {
-        let mut new_states: IndexMap<State, Rc<RefCell<State>>> = IndexMap::new();
-        for state in self.iter() {
-            let state_entry = new_states
-                .entry(state.borrow().deref().clone())
-                .and_modify(|entry| {
-                    let mut borrow_mut = entry.borrow_mut();
-                    if !borrow_mut.incoming.contains(state) {
-                        borrow_mut.incoming.extend(vec![state.clone()]);
-                    }
-                    borrow_mut.outgoing.extend(state.borrow().outgoing.clone());
-                    borrow_mut.items.extend(state.borrow().items.clone());
-                })
-                .or_insert(Rc::clone(state));
-
-            {
-                let incoming_state = &state_entry.borrow().incoming;
-                for i_state in incoming_state.iter().cloned() {
-                    let mut outgoing_map = IndexMap::new();
-                    {
-                        let i_state_ = i_state.borrow();
-                        {
-                            for (o_symbol, o_state) in i_state_.outgoing.iter() {
-                                if o_state.borrow().deref().eq(state.borrow().deref()) {
-                                    outgoing_map.insert(o_symbol.clone(), Rc::clone(state_entry));
-                                }
-                            }
-                        }
+        let mut new_states: IndexMap<State, Rc<RefCell<StateNode>>> = IndexMap::new();
+        for node in self.iter() {
+            let state = node.borrow().state.clone();
+            let state_entry = match new_states.get(&state) {
+                Some(entry) => {
+                    //everything is read before the entry is borrowed mutably,
+                    //the states may link to each other or to themselves
+                    let (outgoing, items) = {
+                        let node = node.borrow();
+                        (node.outgoing.clone(), node.state.items.clone())
+                    };
+                    let known = entry
+                        .borrow()
+                        .incoming
+                        .iter()
+                        .any(|incoming| incoming.borrow().state == state);
+                    let mut entry_ = entry.borrow_mut();
+                    if !known {
+                        entry_.incoming.push(Rc::clone(node));
                     }
-                    let mut _i_state_ = i_state.borrow_mut();
-                    _i_state_.outgoing.extend(outgoing_map);
+                    entry_.outgoing.extend(outgoing);
+                    entry_.state.items.extend(items);
+                    Rc::clone(entry)
+                }
+                None => {
+                    new_states.insert(state.clone(), Rc::clone(node));
+                    Rc::clone(node)
                 }
+            };
+
+            let incoming = state_entry.borrow().incoming.clone();
+            for i_state in incoming {
+                let outgoing_map: IndexMap<SymbolId, Rc<RefCell<StateNode>>> = i_state
+                    .borrow()
+                    .outgoing
+                    .iter()
+                    .filter(|(_, o_state)| o_state.borrow().state == state)
+                    .map(|(o_symbol, _)| (*o_symbol, Rc::clone(&state_entry)))
+                    .collect();
+                i_state.borrow_mut().outgoing.extend(outgoing_map);
             }
-            state_entry.borrow_mut().items.merge_cores();
+            state_entry.borrow_mut().state.items.merge_cores();
         }
         let a = new_states.into_values().collect::<Vec<_>>();
         self.clear();
         self.extend(a);
     }
 
-    fn custom_get(&self, state: &Rc<RefCell<State>>) -> Option<Rc<RefCell<State>>> {
+    fn custom_get(&self, state: &Rc<RefCell<StateNode>>) -> Option<Rc<RefCell<StateNode>>> {
         self.iter()
+            .find(|state_ref| state_ref.borrow().state == state.borrow().state)
             .cloned()
-            .find(|state_ref| state_ref.borrow().clone().eq(&state.borrow().clone()))
     }
 
-    fn custom_contains(&self, other: &Rc<RefCell<State>>) -> bool {
+    fn custom_contains(&self, other: &Rc<RefCell<StateNode>>) -> bool {
+        let other = &other.borrow().state;
         self.iter().any(|state| {
-            state
-                .borrow()
-                .items
-                .iter()
-                .any(|item| other.borrow().items.contains(item))
-                && state.borrow().transition_symbol == other.borrow().transition_symbol
-                && state.borrow().index == other.borrow().index
+            let state = &state.borrow().state;
+            state.items.iter().any(|item| other.items.contains(item))
+                && state.transition_symbol == other.transition_symbol
+                && state.index == other.index
         })
     }
 }
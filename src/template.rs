@@ -0,0 +1,184 @@
+use crate::{
+    error::GrammarError,
+    grammar::Grammar,
+    interner::Interner,
+    predicate::Predicate,
+    production::{Production, SemanticAction, SourceTokens, ValueAction},
+    symbol::{Symbol, SymbolId},
+    token::TokenKind,
+    value::ValueType,
+};
+
+///Parameterised nonterminal like `Comma<T> -> T | Comma<T> CommaToken T`,
+///instantiated on demand into concrete nonterminals named `Comma<Expr>`
+#[derive(Clone)]
+pub struct Template<AST, Token, TranslatorStack> {
+    pub params: Vec<String>,
+    pub alternatives: Vec<TemplateAlternative<AST, Token, TranslatorStack>>,
+}
+
+///Alternative of a template, its action is shared by every instantiation
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub struct TemplateAlternative<AST, Token, TranslatorStack> {
+    //symbol name and template arguments, ex: ("Comma", ["T"]) or ("T", [])
+    pub body: Vec<(String, Vec<String>)>,
+    pub error_message: Option<String>,
+    pub action_tokens: SourceTokens,
+    pub action: Option<SemanticAction<AST, Token, TranslatorStack>>,
+    pub value_action_tokens: SourceTokens,
+    pub value_action: Option<ValueAction>,
+    //argument and value types of the typed action as written, parameters
+    //stand for SemanticValue
+    pub argument_types: Option<Vec<ValueType>>,
+    pub value_type: Option<ValueType>,
+    pub predicate_tokens: SourceTokens,
+    pub predicate: Option<Predicate<AST, Token>>,
+}
+
+impl<AST, Token, TranslatorStack> std::fmt::Debug for Template<AST, Token, TranslatorStack> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let alternatives: Vec<&Vec<(String, Vec<String>)>> = self
+            .alternatives
+            .iter()
+            .map(|alternative| &alternative.body)
+            .collect();
+        f.debug_struct("Template")
+            .field("params", &self.params)
+            .field("alternatives", &alternatives)
+            .finish()
+    }
+}
+
+impl<AST, Token, TranslatorStack> Grammar<AST, Token, TranslatorStack>
+where
+    AST: Clone,
+    Token: Clone + TokenKind,
+    TranslatorStack: Clone,
+{
+    ///Adds an alternative to a template, templates have to be defined before
+    ///they are instantiated. Err when the template is already defined with
+    ///other parameters.
+    pub fn define_template(
+        &mut self,
+        name: &str,
+        params: &[&str],
+        alternative: TemplateAlternative<AST, Token, TranslatorStack>,
+    ) -> Result<(), GrammarError> {
+        let params: Vec<String> = params.iter().map(|param| param.to_string()).collect();
+        let template = self.templates.entry(name.to_string()).or_insert(Template {
+            params: params.clone(),
+            alternatives: vec![],
+        });
+        if template.params != params {
+            return Err(GrammarError::InvalidTemplate(format!(
+                "template {name} is declared with parameters {} and {}",
+                template.params.join(", "),
+                params.join(", ")
+            )));
+        }
+        template.alternatives.push(alternative);
+        Ok(())
+    }
+
+    ///Returns the nonterminal `name<args>`, creating its productions the first
+    ///time it is used. Err when the template is not defined or takes another
+    ///number of arguments.
+    pub fn instantiate(&mut self, name: &str, args: &[&str]) -> Result<SymbolId, GrammarError> {
+        let symbol = Symbol::NONTERMINAL(format!("{name}<{}>", args.join(", ")));
+        if let Some(id) = self.symbols.reverse_lookup(&symbol) {
+            return Ok(id);
+        }
+        let Some(template) = self.templates.get(name).cloned() else {
+            return Err(GrammarError::InvalidTemplate(format!(
+                "template {name} is not defined"
+            )));
+        };
+        if template.params.len() != args.len() {
+            return Err(GrammarError::InvalidTemplate(format!(
+                "template {name} takes {} arguments but is given {}",
+                template.params.len(),
+                args.len()
+            )));
+        }
+        //interned before the alternatives so recursive uses find it
+        let head = self.symbols.intern(symbol);
+        let substitute = |symbol: &String| -> String {
+            match template.params.iter().position(|param| param == symbol) {
+                Some(index) => args[index].to_string(),
+                None => symbol.clone(),
+            }
+        };
+        for alternative in template.alternatives.iter() {
+            let body = alternative
+                .body
+                .iter()
+                .map(|(symbol, symbol_args)| {
+                    if symbol_args.is_empty() {
+                        Ok(self.symbols.intern(Symbol::NONTERMINAL(substitute(symbol))))
+                    } else {
+                        let symbol_args: Vec<String> = symbol_args.iter().map(substitute).collect();
+                        let symbol_args: Vec<&str> =
+                            symbol_args.iter().map(|arg| arg.as_str()).collect();
+                        self.instantiate(symbol, &symbol_args)
+                    }
+                })
+                .collect::<Result<_, _>>()?;
+            let production = Production {
+                head,
+                body,
+                error_message: alternative.error_message.clone(),
+                action: alternative.action.clone(),
+                action_tokens: alternative.action_tokens.clone(),
+                value_action: alternative.value_action.clone(),
+                value_action_tokens: alternative.value_action_tokens.clone(),
+                argument_types: alternative.argument_types.as_ref().map(|argument_types| {
+                    argument_types
+                        .iter()
+                        .map(|argument_type| untyped_params(argument_type, &template.params))
+                        .collect()
+                }),
+                value_type: alternative
+                    .value_type
+                    .as_ref()
+                    .map(|value_type| untyped_params(value_type, &template.params)),
+                predicate: alternative.predicate.clone(),
+                predicate_tokens: alternative.predicate_tokens.clone(),
+                index: self.productions.vec.len(),
+            };
+            self.productions.intern(production);
+            if let Some(value_type) = &alternative.value_type {
+                self.declare_value_type(head, untyped_params(value_type, &template.params));
+            }
+        }
+        Ok(head)
+    }
+}
+
+//`value_type` named with the template parameters replaced by SemanticValue,
+//the type they have in typed actions: `Vec<SemanticValue>` for `Vec<T>`
+fn untyped_params(value_type: &ValueType, params: &[String]) -> ValueType {
+    ValueType {
+        name: untyped_name(&value_type.name, params),
+        id: value_type.id,
+    }
+}
+
+fn untyped_name(value_type: &str, params: &[String]) -> String {
+    let mut untyped = String::new();
+    let mut ident = String::new();
+    for c in value_type.chars().chain(std::iter::once(' ')) {
+        if c.is_alphanumeric() || c == '_' {
+            ident.push(c);
+            continue;
+        }
+        match params.contains(&ident) {
+            true => untyped.push_str("SemanticValue"),
+            false => untyped.push_str(&ident),
+        }
+        ident.clear();
+        untyped.push(c);
+    }
+    untyped.pop();
+    untyped
+}
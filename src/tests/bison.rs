@@ -0,0 +1,70 @@
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack, AST};
+
+#[test]
+fn bison() {
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> E;
+
+        E -> E P T { |_ctx| {} } | T;
+
+        [terminal_productions]
+
+        P -> [Token::A];
+
+        T -> [Token::C];
+    );
+    let y = grammar.to_bison();
+    assert!(y.contains("%token A \"A\""));
+    assert!(y.contains("%token C \"C\""));
+    assert!(!y.contains("EOF"));
+    assert!(y.contains("%start Start"));
+    let rules: Vec<&str> = y
+        .lines()
+        .filter(|line| line.contains(": "))
+        .map(|line| line.split(" /*").next().unwrap())
+        .collect();
+    assert_eq!(rules, vec!["Start: E", "E: E P T", "E: T", "P: A", "T: C"]);
+}
+
+#[test]
+fn bison_precedences() {
+    let grammar: Grammar<AST, Token, TranslatorStack> = GrammarBuilder::new()
+        .terminal(Token::A)
+        .terminal(Token::B)
+        .terminal(Token::C)
+        .terminal("Number")
+        .left([Token::A])
+        .right([Token::B])
+        .nonassoc([Token::C])
+        .rule("Start")
+        .seq(["E"])
+        .rule("E")
+        .seq(["E", "A", "E"])
+        .seq(["E", "B", "E"])
+        .seq(["B", "E"])
+        .prec(Token::C)
+        .seq(["Number"])
+        .build()
+        .unwrap();
+    let y = grammar.to_bison();
+    let declarations: Vec<&str> = y
+        .lines()
+        .filter(|line| {
+            line.starts_with("%left") || line.starts_with("%right") || line.starts_with("%nonassoc")
+        })
+        .collect();
+    assert_eq!(declarations, ["%left A", "%right B", "%nonassoc C"]);
+    let rules: Vec<&str> = y.lines().filter(|line| line.contains(": ")).collect();
+    assert_eq!(
+        rules,
+        [
+            "Start: E",
+            "E: E A E",
+            "E: E B E",
+            "E: B E %prec C",
+            "E: Number",
+        ]
+    );
+}
@@ -0,0 +1,170 @@
+use logos::Logos;
+
+use crate::error::{GrammarError, ParseErrorKind};
+use crate::prelude::*;
+use crate::tests::{TranslatorStack, AST};
+use crate::token::TokenKind;
+
+#[derive(Logos, TokenKind, Clone, Debug, PartialEq)]
+#[logos(skip r"[ \t\n\r]+")]
+pub(super) enum Token {
+    #[token("+")]
+    Plus,
+    #[token("*")]
+    Star,
+    #[token("<")]
+    Less,
+    #[regex("[0-9]+", |lex| lex.slice().parse().ok())]
+    Number(i64),
+    #[token_kind(error)]
+    Error,
+    #[token_kind(eof)]
+    Eof,
+}
+
+pub(super) fn number(values: Vec<SemanticValue>) -> Result<SemanticValue, String> {
+    let mut values = values.into_iter();
+    match take_value::<Token>(&mut values, "Expr")? {
+        Token::Number(number) => Ok(Box::new(number)),
+        _ => unreachable!(),
+    }
+}
+
+pub(super) fn binary(
+    op: fn(i64, i64) -> i64,
+) -> impl Fn(Vec<SemanticValue>) -> Result<SemanticValue, String> {
+    move |values| {
+        let mut values = values.into_iter();
+        let left: i64 = take_value(&mut values, "Expr")?;
+        let _: Token = take_value(&mut values, "Expr")?;
+        let right: i64 = take_value(&mut values, "Expr")?;
+        Ok(Box::new(op(left, right)))
+    }
+}
+
+fn calculator() -> GrammarBuilder<AST, Token, TranslatorStack> {
+    GrammarBuilder::new()
+        .terminal(Token::Plus)
+        .terminal(Token::Star)
+        .terminal(Token::Less)
+        .terminal("Number")
+        .nonassoc([Token::Less])
+        .left([Token::Plus])
+        .left([Token::Star])
+        .rule("Start")
+        .seq(["Expr"])
+        .value_action(|mut values| Ok(values.remove(0)))
+        .rule("Expr")
+        .seq(["Expr", "Plus", "Expr"])
+        .value_action(binary(|a, b| a + b))
+        .seq(["Expr", "Star", "Expr"])
+        .value_action(binary(|a, b| a * b))
+        .seq(["Expr", "Less", "Expr"])
+        .value_action(binary(|a, b| (a < b) as i64))
+        .seq(["Number"])
+        .value_action(number)
+}
+
+#[test]
+fn precedence() {
+    let grammar = calculator().build().unwrap();
+    let parser = LR1_Parser::new(grammar);
+    assert!(!parser.conflicts);
+    let mut errors = Vec::new();
+    let mut ast = AST::new();
+    let value = parser.parse(Token::lexer("1 + 2 * 3 + 4"), &mut errors, &mut ast);
+    assert_eq!(
+        *value.unwrap().value.unwrap().downcast::<i64>().unwrap(),
+        11
+    );
+    let value = parser.parse(Token::lexer("1 < 2 + 3"), &mut errors, &mut ast);
+    assert_eq!(*value.unwrap().value.unwrap().downcast::<i64>().unwrap(), 1);
+    assert!(errors.is_empty());
+
+    //non associative
+    let outcome = parser
+        .parse(Token::lexer("1 < 2 < 3"), &mut errors, &mut ast)
+        .unwrap();
+    assert!(outcome.recovered);
+    assert!(outcome.value.is_none());
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].span, 6..7);
+    //the end of the input comes right after the recovery
+    assert!(!errors[0].cascaded && errors[1].cascaded);
+}
+
+#[test]
+fn unresolved_conflicts() {
+    let grammar = GrammarBuilder::<AST, Token, TranslatorStack>::new()
+        .terminal("Plus")
+        .terminal("Number")
+        .rule("Start")
+        .seq(["Expr"])
+        .rule("Expr")
+        .seq(["Expr", "Plus", "Expr"])
+        .seq(["Number"])
+        .build()
+        .unwrap();
+    let parser = LR1_Parser::new(grammar);
+    assert!(parser.conflicts);
+}
+
+#[test]
+fn build_errors() {
+    let error = calculator().rule("Expr").seq(["Term"]).build().err();
+    assert_eq!(
+        error,
+        Some(GrammarError::UndefinedNonterminals(vec![String::from(
+            "Term"
+        )]))
+    );
+    let error = calculator().right(["Minus"]).build().err();
+    assert_eq!(
+        error,
+        Some(GrammarError::UndeclaredTerminals(vec![String::from(
+            "Minus"
+        )]))
+    );
+    let error = GrammarBuilder::<AST, Token, TranslatorStack>::new()
+        .action(|_| {})
+        .build()
+        .err();
+    assert_eq!(
+        error,
+        Some(GrammarError::InvalidBuilderCall(String::from(
+            "action() called before seq()"
+        )))
+    );
+}
+
+#[test]
+fn failed_value_action() {
+    //builder actions are not checked, a wrong downcast is a semantic error
+    let grammar = GrammarBuilder::<AST, Token, TranslatorStack>::new()
+        .terminal("Number")
+        .rule("Start")
+        .seq(["Expr"])
+        .value_action(|mut values| Ok(values.remove(0)))
+        .rule("Expr")
+        .seq(["Number"])
+        .value_action(|values| {
+            let number: i64 = take_value(&mut values.into_iter(), "Expr")?;
+            Ok(Box::new(number))
+        })
+        .build()
+        .unwrap();
+    let parser = LR1_Parser::new(grammar);
+    let mut errors = Vec::new();
+    let mut ast = AST::new();
+    let outcome = parser
+        .parse(Token::lexer("1"), &mut errors, &mut ast)
+        .unwrap();
+    assert!(outcome.value.is_none());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ParseErrorKind::Semantic);
+    assert_eq!(errors[0].span, 0..1);
+    assert_eq!(
+        errors[0].message(),
+        "semantic value in production of Expr is not of type i64"
+    );
+}
@@ -0,0 +1,133 @@
+use std::{fs, path::PathBuf};
+
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack, AST};
+
+//parser generated from fixture() by Codegen, checked in so that it is
+//compiled with the tests
+mod generated {
+    use crate::prelude::*;
+    use crate::tests::{tokens::Token, TranslatorStack, AST};
+
+    include!("codegen/parser_generated/parser.rs");
+
+    pub(super) fn parser() -> LR1_Parser<AST, Token, TranslatorStack> {
+        get_parser()
+    }
+}
+
+//typed actions, a template, an inline nonterminal, a predicate and a
+//precedence, everything the generated grammar has to carry over
+fn fixture() -> Grammar<AST, Token, TranslatorStack> {
+    let mut grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> Sum => |sum: i64| -> i64 { sum };
+
+        Sum -> Sum Plus Sum => |a: i64, _plus: Token, b: i64| -> i64 { a + b }
+        | Comma<Num> => |nums: Vec<SemanticValue>| -> i64 {
+            nums.into_iter().map(|num| *num.downcast::<i64>().unwrap()).sum()
+        };
+
+        Comma<T> -> T => |t: T| -> Vec<T> { vec![t] }
+        | Comma<T> B T => |list: Vec<T>, _b: (), t: T| -> Vec<T> {
+            let mut list = list;
+            list.push(t);
+            list
+        };
+
+        [terminal_productions]
+
+        #[inline]
+        Plus -> [Token::A] => |token: Token| -> Token { token };
+
+        B -> [Token::B];
+
+        Num -> ?{ |ctx| ctx.values.len() < 16 } ["Number"] => |token: Token| -> i64 {
+            match token {
+                Token::Number(number) => number,
+                _ => unreachable!(),
+            }
+        };
+    );
+    let plus = grammar
+        .symbols
+        .reverse_lookup(&Symbol::TERMINAL(String::from("A")))
+        .unwrap();
+    grammar
+        .precedences
+        .declare(crate::precedence::Associativity::Left, &[plus]);
+    grammar
+}
+
+fn fixture_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src/tests/codegen")
+}
+
+#[test]
+fn generated_parser() {
+    let parser = generated::parser();
+    let built = LR1_Parser::new(fixture());
+    assert_eq!(parser.grammar.value_types, built.grammar.value_types);
+    assert_eq!(parser.grammar.precedences, built.grammar.precedences);
+    assert_eq!(parser.grammar.inline, built.grammar.inline);
+    assert_eq!(
+        parser.grammar.templates.keys().collect::<Vec<_>>(),
+        ["Comma"]
+    );
+    for (generated, production) in parser
+        .grammar
+        .productions
+        .vec
+        .iter()
+        .zip(built.grammar.productions.vec.iter())
+    {
+        assert_eq!(generated.argument_types, production.argument_types);
+        assert_eq!(generated.value_type, production.value_type);
+        assert_eq!(generated.predicate_tokens, production.predicate_tokens);
+    }
+    assert!(parser.grammar.check_value_types().is_ok());
+
+    let mut errors = Vec::new();
+    let mut ast = AST::new();
+    let outcome = parser
+        .parse(Token::lexer("1 b 2 a 3 a 4 b 5"), &mut errors, &mut ast)
+        .unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(*outcome.value.unwrap().downcast::<i64>().unwrap(), 15);
+}
+
+//the checked in parser is the one Codegen writes today, set
+//MANODAE_UPDATE_FIXTURES to write it again
+#[test]
+fn generated_parser_up_to_date() {
+    let out = std::env::temp_dir().join(format!("manodae_codegen_{}", std::process::id()));
+    fs::create_dir_all(&out).unwrap();
+    Codegen::gen(out.clone(), fixture(), ["AST", "Token", "TranslatorStack"]);
+    let generated = out.join("parser_generated");
+    let fixture = fixture_path().join("parser_generated");
+    let update = std::env::var_os("MANODAE_UPDATE_FIXTURES").is_some();
+    let mut files: Vec<PathBuf> = fs::read_dir(&generated)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "rs"))
+        .collect();
+    files.sort();
+    for file in files {
+        let name = file.file_name().unwrap();
+        let code = fs::read_to_string(&file).unwrap();
+        if update {
+            fs::create_dir_all(&fixture).unwrap();
+            fs::write(fixture.join(name), &code).unwrap();
+            continue;
+        }
+        let checked_in = fs::read_to_string(fixture.join(name)).unwrap_or_default();
+        assert!(
+            code == checked_in,
+            "{} is out of date, run the tests with MANODAE_UPDATE_FIXTURES=1",
+            name.to_string_lossy()
+        );
+    }
+    fs::remove_dir_all(&out).ok();
+}
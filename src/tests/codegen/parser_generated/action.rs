@@ -0,0 +1,42 @@
+macro_rules! g {
+    ($ s : expr) => {
+        IndexMap::from($s)
+    };
+}
+pub fn __action__() -> IndexMap<StateId, IndexMap<SymbolId, Action>> {
+    IndexMap::from([
+        (i(0usize), {
+            g! { [(s (11usize) , S (i (5usize)))] }
+        }),
+        (i(1usize), {
+            g! { [(s (1usize) , A)] }
+        }),
+        (i(2usize), {
+            g! { [(s (1usize) , R (p (1usize))) , (s (9usize) , S (i (6usize)))] }
+        }),
+        (i(3usize), {
+            g! { [(s (1usize) , R (p (5usize))) , (s (9usize) , R (p (5usize))) , (s (10usize) , S (i (8usize)))] }
+        }),
+        (i(4usize), {
+            g! { [(s (1usize) , R (p (3usize))) , (s (9usize) , R (p (3usize))) , (s (10usize) , R (p (3usize)))] }
+        }),
+        (i(5usize), {
+            g! { [(s (1usize) , R (p (7usize))) , (s (9usize) , R (p (7usize))) , (s (10usize) , R (p (7usize)))] }
+        }),
+        (i(6usize), {
+            g! { [(s (11usize) , S (i (5usize)))] }
+        }),
+        (i(7usize), {
+            g! { [(s (11usize) , S (i (5usize)))] }
+        }),
+        (i(8usize), {
+            g! { [(s (11usize) , R (p (6usize)))] }
+        }),
+        (i(9usize), {
+            g! { [(s (1usize) , R (p (2usize))) , (s (9usize) , R (p (2usize)))] }
+        }),
+        (i(10usize), {
+            g! { [(s (1usize) , R (p (4usize))) , (s (9usize) , R (p (4usize))) , (s (10usize) , R (p (4usize)))] }
+        }),
+    ])
+}
@@ -0,0 +1,21 @@
+macro_rules! h {
+    ($ s : expr) => {
+        IndexSet::from($s)
+    };
+}
+pub fn __first__() -> IndexMap<SymbolId, IndexSet<SymbolId>> {
+    IndexMap::from([
+        (s(1usize), h! { [s (1usize)] }),
+        (s(3usize), h! { [s (3usize)] }),
+        (s(9usize), h! { [s (9usize)] }),
+        (s(10usize), h! { [s (10usize)] }),
+        (s(11usize), h! { [s (11usize)] }),
+        (s(0usize), h! { [s (11usize)] }),
+        (s(2usize), h! { [s (11usize)] }),
+        (s(4usize), h! { [s (11usize)] }),
+        (s(5usize), h! { [] }),
+        (s(6usize), h! { [s (11usize)] }),
+        (s(7usize), h! { [s (11usize)] }),
+        (s(8usize), h! { [s (10usize)] }),
+    ])
+}
@@ -0,0 +1,16 @@
+macro_rules! h {
+    ($ s : expr) => {
+        IndexSet::from($s)
+    };
+}
+pub fn __follow__() -> IndexMap<SymbolId, IndexSet<SymbolId>> {
+    IndexMap::from([
+        (s(0usize), h! { [] }),
+        (s(2usize), h! { [s (1usize)] }),
+        (s(4usize), h! { [s (1usize) , s (9usize)] }),
+        (s(5usize), h! { [] }),
+        (s(6usize), h! { [s (10usize) , s (1usize) , s (9usize)] }),
+        (s(7usize), h! { [s (10usize) , s (1usize) , s (9usize)] }),
+        (s(8usize), h! { [s (11usize)] }),
+    ])
+}
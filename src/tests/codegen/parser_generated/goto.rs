@@ -0,0 +1,21 @@
+macro_rules! g {
+    ($ s : expr) => {
+        IndexMap::from($s)
+    };
+}
+pub fn __goto__() -> IndexMap<StateId, IndexMap<SymbolId, StateId>> {
+    IndexMap::from([
+        (i(0usize), {
+            g! { [(s (2usize) , i (1usize)) , (s (4usize) , i (2usize)) , (s (6usize) , i (3usize)) , (s (7usize) , i (4usize))] }
+        }),
+        (i(3usize), {
+            g! { [(s (8usize) , i (7usize))] }
+        }),
+        (i(6usize), {
+            g! { [(s (4usize) , i (9usize)) , (s (6usize) , i (3usize)) , (s (7usize) , i (4usize))] }
+        }),
+        (i(7usize), {
+            g! { [(s (7usize) , i (10usize))] }
+        }),
+    ])
+}
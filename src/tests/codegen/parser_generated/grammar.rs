@@ -0,0 +1,34 @@
+macro_rules! f {
+    ($ s : expr) => {
+        String::from($s)
+    };
+}
+macro_rules! e {
+    ($ idx : expr , $ s : ident) => {
+        $s[$idx].clone()
+    };
+}
+macro_rules! d {
+    ($ idx : expr , $ p : ident) => {
+        $p[$idx].clone()
+    };
+}
+#[allow(unused_variables)]
+fn __grammar__() -> Grammar<AST, Token, TranslatorStack> {
+    let y = vec ! [P :: n (0usize , s (0usize) , vec ! [s (2usize)] , None , "None" . parse () . unwrap () , None , "None" . parse () . unwrap () , None , None , None , "None" . parse () . unwrap () , None ,) , P :: n (1usize , s (2usize) , vec ! [s (4usize)] , None , "None" . parse () . unwrap () , None , "Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (unused_mut)] let mut values = values . into_iter () ; let sum : i64 = take_value (& mut values , stringify ! (Start)) ? ; let value : i64 = { sum } ; Ok (Box :: new (value)) }))" . parse () . unwrap () , Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (unused_mut)] let mut values = values . into_iter () ; let sum : i64 = take_value (& mut values , stringify ! (Start)) ? ; let value : i64 = { sum } ; Ok (Box :: new (value)) })) , Some (vec ! [ValueType :: of :: < i64 > ("i64")]) , Some (ValueType :: of :: < i64 > ("i64")) , "None" . parse () . unwrap () , None ,) , P :: n (2usize , s (4usize) , vec ! [s (4usize) , s (9usize) , s (4usize)] , None , "inline_action (None , None , 1usize .. 2usize , 0usize .. 1usize)" . parse () . unwrap () , inline_action (None , None , 1usize .. 2usize , 0usize .. 1usize) , "inline_value_action (Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (unused_mut)] let mut values = values . into_iter () ; let token : Token = take_value (& mut values , stringify ! (Plus)) ? ; let value : Token = { token } ; Ok (Box :: new (value)) })) , Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (unused_mut)] let mut values = values . into_iter () ; let a : i64 = take_value (& mut values , stringify ! (Sum)) ? ; let _plus : Token = take_value (& mut values , stringify ! (Sum)) ? ; let b : i64 = take_value (& mut values , stringify ! (Sum)) ? ; let value : i64 = { a + b } ; Ok (Box :: new (value)) })) , 1usize .. 2usize)" . parse () . unwrap () , inline_value_action (Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (unused_mut)] let mut values = values . into_iter () ; let token : Token = take_value (& mut values , stringify ! (Plus)) ? ; let value : Token = { token } ; Ok (Box :: new (value)) })) , Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (unused_mut)] let mut values = values . into_iter () ; let a : i64 = take_value (& mut values , stringify ! (Sum)) ? ; let _plus : Token = take_value (& mut values , stringify ! (Sum)) ? ; let b : i64 = take_value (& mut values , stringify ! (Sum)) ? ; let value : i64 = { a + b } ; Ok (Box :: new (value)) })) , 1usize .. 2usize) , None , Some (ValueType :: of :: < i64 > ("i64")) , "None" . parse () . unwrap () , None ,) , P :: n (3usize , s (6usize) , vec ! [s (7usize)] , None , "None" . parse () . unwrap () , None , "Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; # [allow (unused_mut)] let mut values = values . into_iter () ; let t : T = take_value (& mut values , stringify ! (Comma)) ? ; let value : Vec < T > = { vec ! [t] } ; Ok (Box :: new (value)) }))" . parse () . unwrap () , Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; # [allow (unused_mut)] let mut values = values . into_iter () ; let t : T = take_value (& mut values , stringify ! (Comma)) ? ; let value : Vec < T > = { vec ! [t] } ; Ok (Box :: new (value)) })) , Some (vec ! [ValueType :: of :: < SemanticValue > ("SemanticValue")]) , Some (ValueType :: of :: < Vec < SemanticValue > > ("Vec<SemanticValue>")) , "None" . parse () . unwrap () , None ,) , P :: n (4usize , s (6usize) , vec ! [s (6usize) , s (8usize) , s (7usize)] , None , "None" . parse () . unwrap () , None , "Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; # [allow (unused_mut)] let mut values = values . into_iter () ; let list : Vec < T > = take_value (& mut values , stringify ! (Comma)) ? ; let _b : () = take_value (& mut values , stringify ! (Comma)) ? ; let t : T = take_value (& mut values , stringify ! (Comma)) ? ; let value : Vec < T > = { let mut list = list ; list . push (t) ; list } ; Ok (Box :: new (value)) }))" . parse () . unwrap () , Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; # [allow (unused_mut)] let mut values = values . into_iter () ; let list : Vec < T > = take_value (& mut values , stringify ! (Comma)) ? ; let _b : () = take_value (& mut values , stringify ! (Comma)) ? ; let t : T = take_value (& mut values , stringify ! (Comma)) ? ; let value : Vec < T > = { let mut list = list ; list . push (t) ; list } ; Ok (Box :: new (value)) })) , Some (vec ! [ValueType :: of :: < Vec < SemanticValue > > ("Vec<SemanticValue>") , ValueType :: of :: < () > ("()") , ValueType :: of :: < SemanticValue > ("SemanticValue")]) , Some (ValueType :: of :: < Vec < SemanticValue > > ("Vec<SemanticValue>")) , "None" . parse () . unwrap () , None ,) , P :: n (5usize , s (4usize) , vec ! [s (6usize)] , None , "None" . parse () . unwrap () , None , "Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (unused_mut)] let mut values = values . into_iter () ; let nums : Vec < SemanticValue > = take_value (& mut values , stringify ! (Sum)) ? ; let value : i64 = { nums . into_iter () . map (| num | * num . downcast ::< i64 > () . unwrap ()) . sum () } ; Ok (Box :: new (value)) }))" . parse () . unwrap () , Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (unused_mut)] let mut values = values . into_iter () ; let nums : Vec < SemanticValue > = take_value (& mut values , stringify ! (Sum)) ? ; let value : i64 = { nums . into_iter () . map (| num | * num . downcast ::< i64 > () . unwrap ()) . sum () } ; Ok (Box :: new (value)) })) , Some (vec ! [ValueType :: of :: < Vec < SemanticValue > > ("Vec<SemanticValue>")]) , Some (ValueType :: of :: < i64 > ("i64")) , "None" . parse () . unwrap () , None ,) , P :: n (6usize , s (8usize) , vec ! [s (10usize)] , None , "None" . parse () . unwrap () , None , "None" . parse () . unwrap () , None , None , None , "None" . parse () . unwrap () , None ,) , P :: n (7usize , s (7usize) , vec ! [s (11usize)] , None , "None" . parse () . unwrap () , None , "Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (unused_mut)] let mut values = values . into_iter () ; let token : Token = take_value (& mut values , stringify ! (Num)) ? ; let value : i64 = { match token { Token :: Number (number) => number , _ => unreachable ! () , } } ; Ok (Box :: new (value)) }))" . parse () . unwrap () , Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (unused_mut)] let mut values = values . into_iter () ; let token : Token = take_value (& mut values , stringify ! (Num)) ? ; let value : i64 = { match token { Token :: Number (number) => number , _ => unreachable ! () , } } ; Ok (Box :: new (value)) })) , Some (vec ! [ValueType :: of :: < Token > ("Token")]) , Some (ValueType :: of :: < i64 > ("i64")) , "Some (semantic_predicate (| ctx | ctx . values . len () < 16))" . parse () . unwrap () , Some (semantic_predicate (| ctx | ctx . values . len () < 16)) ,)] ;
+    let x = vec![
+        N(f! { "S'" }),
+        T(f! { "EOF" }),
+        N(f! { "Start" }),
+        T(f! { "error" }),
+        N(f! { "Sum" }),
+        N(f! { "Plus" }),
+        N(f! { "Comma<Num>" }),
+        N(f! { "Num" }),
+        N(f! { "B" }),
+        T(f! { "A" }),
+        T(f! { "B" }),
+        T(f! { "Number" }),
+    ];
+    Grammar { start : s (2usize) , productions : Productions { map : IndexMap :: from ([(d ! { 0usize , y } , p (0usize)) , (d ! { 1usize , y } , p (1usize)) , (d ! { 2usize , y } , p (2usize)) , (d ! { 3usize , y } , p (3usize)) , (d ! { 4usize , y } , p (4usize)) , (d ! { 5usize , y } , p (5usize)) , (d ! { 6usize , y } , p (6usize)) , (d ! { 7usize , y } , p (7usize))]) , vec : y , } , production_head_map : IndexMap :: new () , symbols : Symbols { map : IndexMap :: from ([(e ! { 0usize , x } , s (0usize)) , (e ! { 1usize , x } , s (1usize)) , (e ! { 2usize , x } , s (2usize)) , (e ! { 3usize , x } , s (3usize)) , (e ! { 4usize , x } , s (4usize)) , (e ! { 5usize , x } , s (5usize)) , (e ! { 6usize , x } , s (6usize)) , (e ! { 7usize , x } , s (7usize)) , (e ! { 8usize , x } , s (8usize)) , (e ! { 9usize , x } , s (9usize)) , (e ! { 10usize , x } , s (10usize)) , (e ! { 11usize , x } , s (11usize))]) , vec : x , terminals : vec ! [s (1usize) , s (3usize) , s (9usize) , s (10usize) , s (11usize)] , non_terminals : vec ! [s (0usize) , s (2usize) , s (4usize) , s (5usize) , s (6usize) , s (7usize) , s (8usize)] , } , value_types : IndexMap :: from ([(s (2usize) , ValueType :: of :: < i64 > ("i64")) , (s (4usize) , ValueType :: of :: < i64 > ("i64")) , (s (6usize) , ValueType :: of :: < Vec < SemanticValue > > ("Vec<SemanticValue>")) , (s (5usize) , ValueType :: of :: < Token > ("Token")) , (s (7usize) , ValueType :: of :: < i64 > ("i64"))]) , precedences : Precedences { terminals : IndexMap :: from ([(s (9usize) , Precedence { level : 1usize , associativity : Associativity :: Left })]) , productions : IndexMap :: from ([]) , } , templates : IndexMap :: from ([(f ! { "Comma" } , Template { params : vec ! [f ! { "T" }] , alternatives : vec ! [TemplateAlternative { body : vec ! [(f ! { "T" } , vec ! [])] , error_message : None , action_tokens : "None" . parse () . unwrap () , action : None , value_action_tokens : "Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; # [allow (unused_mut)] let mut values = values . into_iter () ; let t : T = take_value (& mut values , stringify ! (Comma)) ? ; let value : Vec < T > = { vec ! [t] } ; Ok (Box :: new (value)) }))" . parse () . unwrap () , value_action : Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; # [allow (unused_mut)] let mut values = values . into_iter () ; let t : T = take_value (& mut values , stringify ! (Comma)) ? ; let value : Vec < T > = { vec ! [t] } ; Ok (Box :: new (value)) })) , argument_types : Some ({ # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; vec ! [ValueType :: of :: < T > ("T")] }) , value_type : Some ({ # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; ValueType :: of :: < Vec < T > > ("Vec<T>") }) , predicate_tokens : "None" . parse () . unwrap () , predicate : None , } , TemplateAlternative { body : vec ! [(f ! { "Comma" } , vec ! [f ! { "T" }]) , (f ! { "B" } , vec ! []) , (f ! { "T" } , vec ! [])] , error_message : None , action_tokens : "None" . parse () . unwrap () , action : None , value_action_tokens : "Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; # [allow (unused_mut)] let mut values = values . into_iter () ; let list : Vec < T > = take_value (& mut values , stringify ! (Comma)) ? ; let _b : () = take_value (& mut values , stringify ! (Comma)) ? ; let t : T = take_value (& mut values , stringify ! (Comma)) ? ; let value : Vec < T > = { let mut list = list ; list . push (t) ; list } ; Ok (Box :: new (value)) }))" . parse () . unwrap () , value_action : Some (Arc :: new (| values : Vec < SemanticValue > | -> Result < SemanticValue , String > { # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; # [allow (unused_mut)] let mut values = values . into_iter () ; let list : Vec < T > = take_value (& mut values , stringify ! (Comma)) ? ; let _b : () = take_value (& mut values , stringify ! (Comma)) ? ; let t : T = take_value (& mut values , stringify ! (Comma)) ? ; let value : Vec < T > = { let mut list = list ; list . push (t) ; list } ; Ok (Box :: new (value)) })) , argument_types : Some ({ # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; vec ! [ValueType :: of :: < Vec < T > > ("Vec<T>") , ValueType :: of :: < () > ("()") , ValueType :: of :: < T > ("T")] }) , value_type : Some ({ # [allow (non_camel_case_types , dead_code)] type T = SemanticValue ; ValueType :: of :: < Vec < T > > ("Vec<T>") }) , predicate_tokens : "None" . parse () . unwrap () , predicate : None , }] , })]) , inline : IndexSet :: from ([]) , mid_rules : IndexMap :: from ([]) , errors : vec ! [] , }
+}
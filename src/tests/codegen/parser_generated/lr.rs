@@ -0,0 +1,103 @@
+pub fn __lr__() -> States {
+    let z = vec![
+        a::new(
+            0usize,
+            vec![
+                I::n(p(0usize), 0u8, vec![s(1usize)]),
+                I::n(p(1usize), 0u8, vec![s(1usize)]),
+                I::n(p(2usize), 0u8, vec![s(1usize), s(9usize)]),
+                I::n(p(5usize), 0u8, vec![s(1usize), s(9usize)]),
+                I::n(p(3usize), 0u8, vec![s(1usize), s(9usize), s(10usize)]),
+                I::n(p(4usize), 0u8, vec![s(1usize), s(9usize), s(10usize)]),
+                I::n(p(7usize), 0u8, vec![s(1usize), s(9usize), s(10usize)]),
+            ],
+            s(0usize),
+        ),
+        a::new(
+            1usize,
+            vec![I::n(p(0usize), 1u8, vec![s(1usize)])],
+            s(2usize),
+        ),
+        a::new(
+            2usize,
+            vec![
+                I::n(p(1usize), 1u8, vec![s(1usize)]),
+                I::n(p(2usize), 1u8, vec![s(1usize), s(9usize)]),
+            ],
+            s(4usize),
+        ),
+        a::new(
+            3usize,
+            vec![
+                I::n(p(5usize), 1u8, vec![s(1usize), s(9usize)]),
+                I::n(p(4usize), 1u8, vec![s(1usize), s(9usize), s(10usize)]),
+                I::n(p(6usize), 0u8, vec![s(11usize)]),
+            ],
+            s(6usize),
+        ),
+        a::new(
+            4usize,
+            vec![I::n(p(3usize), 1u8, vec![s(1usize), s(9usize), s(10usize)])],
+            s(7usize),
+        ),
+        a::new(
+            5usize,
+            vec![I::n(p(7usize), 1u8, vec![s(1usize), s(9usize), s(10usize)])],
+            s(11usize),
+        ),
+        a::new(
+            6usize,
+            vec![
+                I::n(p(2usize), 2u8, vec![s(1usize), s(9usize)]),
+                I::n(p(2usize), 0u8, vec![s(1usize), s(9usize)]),
+                I::n(p(5usize), 0u8, vec![s(1usize), s(9usize)]),
+                I::n(p(3usize), 0u8, vec![s(1usize), s(9usize), s(10usize)]),
+                I::n(p(4usize), 0u8, vec![s(1usize), s(9usize), s(10usize)]),
+                I::n(p(7usize), 0u8, vec![s(1usize), s(9usize), s(10usize)]),
+            ],
+            s(9usize),
+        ),
+        a::new(
+            7usize,
+            vec![
+                I::n(p(4usize), 2u8, vec![s(1usize), s(9usize), s(10usize)]),
+                I::n(p(7usize), 0u8, vec![s(1usize), s(9usize), s(10usize)]),
+            ],
+            s(8usize),
+        ),
+        a::new(
+            8usize,
+            vec![I::n(p(6usize), 1u8, vec![s(11usize)])],
+            s(10usize),
+        ),
+        a::new(
+            9usize,
+            vec![
+                I::n(p(2usize), 3u8, vec![s(1usize), s(9usize)]),
+                I::n(p(2usize), 1u8, vec![s(1usize), s(9usize)]),
+            ],
+            s(4usize),
+        ),
+        a::new(
+            13usize,
+            vec![I::n(p(4usize), 3u8, vec![s(1usize), s(9usize), s(10usize)])],
+            s(7usize),
+        ),
+    ];
+    States {
+        map: IndexMap::from([
+            (z[0usize].clone(), i(0usize)),
+            (z[1usize].clone(), i(1usize)),
+            (z[2usize].clone(), i(2usize)),
+            (z[3usize].clone(), i(3usize)),
+            (z[4usize].clone(), i(4usize)),
+            (z[5usize].clone(), i(5usize)),
+            (z[6usize].clone(), i(6usize)),
+            (z[7usize].clone(), i(7usize)),
+            (z[8usize].clone(), i(8usize)),
+            (z[9usize].clone(), i(9usize)),
+            (z[10usize].clone(), i(10usize)),
+        ]),
+        vec: z,
+    }
+}
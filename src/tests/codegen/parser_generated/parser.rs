@@ -0,0 +1,36 @@
+include!("grammar.rs");
+include!("lr.rs");
+include!("first.rs");
+include!("follow.rs");
+include!("action.rs");
+include!("goto.rs");
+fn get_parser() -> LR1_Parser<AST, Token, TranslatorStack> {
+    LR1_Parser {
+        grammar: __grammar__(),
+        LR1_automata: __lr__(),
+        follow_set: __follow__(),
+        first_set: __first__(),
+        nullable: IndexSet::new(),
+        conflicts: false,
+        goto: __goto__(),
+        action: __action__(),
+        predicated_actions: IndexMap::from([]),
+        terminal_table: vec![
+            Some(s(9usize)),
+            Some(s(10usize)),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(s(11usize)),
+            Some(s(3usize)),
+            Some(s(1usize)),
+        ],
+        warnings: vec![],
+        repair_input: false,
+        item_closure_map: IndexMap::new(),
+        closure_map: IndexMap::new(),
+    }
+}
@@ -0,0 +1,313 @@
+use logos::Logos;
+
+use crate::compose::Merge;
+use crate::error::GrammarError;
+use crate::grammar;
+use crate::prelude::*;
+use crate::production::ProductionId;
+use crate::symbol::Symbol;
+use crate::tests::builder::{binary, number, Token};
+use crate::tests::{TranslatorStack, AST};
+
+fn expression() -> Grammar<AST, Token, TranslatorStack> {
+    GrammarBuilder::new()
+        .terminal(Token::Plus)
+        .terminal("Number")
+        .left([Token::Plus])
+        .rule("Start")
+        .seq(["Expr"])
+        .rule("Expr")
+        .seq(["Expr", "Plus", "Expr"])
+        .value_action(binary(|a, b| a + b))
+        .seq(["Number"])
+        .value_action(number)
+        .build()
+        .unwrap()
+}
+
+fn statement() -> Grammar<AST, Token, TranslatorStack> {
+    GrammarBuilder::new()
+        .terminal(Token::Less)
+        .terminal("Number")
+        .rule("Start")
+        .seq(["Stmt"])
+        .value_action(|mut values| Ok(values.remove(0)))
+        .rule("Stmt")
+        .seq(["Less", "Value"])
+        .value_action(|mut values| Ok(values.remove(1)))
+        .rule("Value")
+        .seq(["Number"])
+        .value_action(number)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn import_and_override() {
+    let expression = expression();
+    let mut grammar = statement();
+    let import = grammar.import(&expression, "expr").unwrap();
+    let expr = grammar
+        .symbols
+        .reverse_lookup(&Symbol::NONTERMINAL(String::from("expr::Expr")))
+        .unwrap();
+    let imported_expr = expression
+        .symbols
+        .reverse_lookup(&Symbol::NONTERMINAL(String::from("Expr")))
+        .unwrap();
+    assert_eq!(import.symbols[&imported_expr], expr);
+    assert_eq!(import.productions.len(), 3);
+
+    let mut import = import;
+    let merged = grammar
+        .merge_rule("Value", &expression, "Expr", "expr", Merge::Override)
+        .unwrap();
+    assert_eq!(merged.productions.len(), 2);
+    //the imported productions moved down in place of Value -> Number
+    import.renumber(&merged);
+    for (from, id) in import.productions.iter() {
+        let production = &grammar.productions.vec[id.0];
+        let imported = &expression.productions.vec[from.0];
+        assert_eq!(production.head, import.symbols[&imported.head]);
+        let body: Vec<SymbolId> = imported
+            .body
+            .iter()
+            .map(|symbol| import.symbols[symbol])
+            .collect();
+        assert_eq!(production.body, body);
+    }
+    let parser = LR1_Parser::new(grammar);
+    assert!(!parser.conflicts);
+    let mut errors = Vec::new();
+    let mut ast = AST::new();
+    let value = parser.parse(Token::lexer("< 1 + 2 + 3"), &mut errors, &mut ast);
+    assert_eq!(*value.unwrap().value.unwrap().downcast::<i64>().unwrap(), 6);
+}
+
+#[test]
+fn conflicts() {
+    let expression = expression();
+    let mut grammar = statement();
+    grammar.import(&expression, "expr").unwrap();
+    assert_eq!(
+        grammar.import(&expression, "expr"),
+        Err(GrammarError::ImportConflicts(vec![
+            String::from("nonterminal expr::Start is already defined"),
+            String::from("nonterminal expr::Expr is already defined"),
+        ]))
+    );
+
+    let mut grammar = statement();
+    grammar.import(&expression, "expr").unwrap();
+    assert_eq!(
+        grammar.merge_rule("Value", &expression, "Expr", "expr", Merge::Extend),
+        Err(GrammarError::ImportConflicts(vec![String::from(
+            "Value already has the alternative Number"
+        )]))
+    );
+}
+
+#[test]
+fn merge_keeps_precedences() {
+    //unary < binds tighter than +
+    let expression: Grammar<AST, Token, TranslatorStack> = GrammarBuilder::new()
+        .terminal(Token::Plus)
+        .terminal(Token::Less)
+        .terminal(Token::Star)
+        .terminal("Number")
+        .left([Token::Plus])
+        .left([Token::Star])
+        .rule("Start")
+        .seq(["Expr"])
+        .rule("Expr")
+        .seq(["Expr", "Plus", "Expr"])
+        .value_action(binary(|a, b| a + b))
+        .seq(["Less", "Expr"])
+        .prec(Token::Star)
+        .value_action(|mut values| {
+            let value: i64 = take_value(&mut values.drain(1..), "Expr")?;
+            Ok(Box::new(-value))
+        })
+        .seq(["Number"])
+        .value_action(number)
+        .build()
+        .unwrap();
+    let mut grammar: Grammar<AST, Token, TranslatorStack> = GrammarBuilder::new()
+        .terminal("Number")
+        .rule("Start")
+        .seq(["Value"])
+        .value_action(|mut values| Ok(values.remove(0)))
+        .rule("Value")
+        .seq(["Number"])
+        .value_action(number)
+        .build()
+        .unwrap();
+    grammar.import(&expression, "expr").unwrap();
+    let merged = grammar
+        .merge_rule("Value", &expression, "Expr", "expr", Merge::Override)
+        .unwrap();
+    let star = grammar
+        .symbols
+        .reverse_lookup(&Symbol::TERMINAL(String::from("Star")))
+        .unwrap();
+    let unary = merged.productions[&ProductionId(3)];
+    assert_eq!(grammar.precedences.productions[&unary], star);
+
+    let parser = LR1_Parser::new(grammar);
+    assert!(!parser.conflicts);
+    let mut errors = Vec::new();
+    let mut ast = AST::new();
+    let value = parser.parse(Token::lexer("< 1 + 2"), &mut errors, &mut ast);
+    assert_eq!(*value.unwrap().value.unwrap().downcast::<i64>().unwrap(), 1);
+}
+
+#[test]
+fn merge_brings_precedence_levels() {
+    let negation: Grammar<AST, Token, TranslatorStack> = GrammarBuilder::new()
+        .terminal(Token::Less)
+        .terminal(Token::Star)
+        .terminal("Number")
+        .left([Token::Star])
+        .rule("Start")
+        .seq(["Neg"])
+        .rule("Neg")
+        .seq(["Less", "Number"])
+        .prec(Token::Star)
+        .build()
+        .unwrap();
+    let mut grammar = statement();
+    let merged = grammar
+        .merge_rule("Value", &negation, "Neg", "neg", Merge::Extend)
+        .unwrap();
+    let star = grammar
+        .symbols
+        .reverse_lookup(&Symbol::TERMINAL(String::from("Star")))
+        .unwrap();
+    assert!(grammar.precedences.terminals.contains_key(&star));
+    let negation_id = merged.productions[&ProductionId(2)];
+    assert_eq!(grammar.precedences.productions[&negation_id], star);
+}
+
+#[test]
+fn value_type_conflicts() {
+    let numbers: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> Value => |v: i64| -> i64 { v };
+
+        [terminal_productions]
+
+        Value -> [Token::Number(0)] => |n: Token| -> i64 {
+            match n {
+                Token::Number(n) => n,
+                _ => unreachable!(),
+            }
+        };
+    );
+    let words: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> Value => |v: String| -> String { v };
+
+        [terminal_productions]
+
+        Value -> [Token::Less] => |l: Token| -> String { l.to_string() };
+    );
+    let mut grammar = numbers;
+    assert_eq!(
+        grammar.merge_rule("Value", &words, "Value", "words", Merge::Extend),
+        Err(GrammarError::ImportConflicts(vec![String::from(
+            "Value is declared with value type i64 and String"
+        )]))
+    );
+    //overriding replaces the value type along with the alternatives
+    grammar
+        .merge_rule("Value", &words, "Value", "words", Merge::Override)
+        .unwrap();
+    let value = grammar
+        .symbols
+        .reverse_lookup(&Symbol::NONTERMINAL(String::from("Value")))
+        .unwrap();
+    assert_eq!(grammar.value_types[&value].name, "String");
+}
+
+#[test]
+fn import_inline() {
+    let expression: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> Expr;
+
+        Expr -> Expr Op Num
+        | Num;
+
+        #[inline]
+        Op -> Plus
+        | Star;
+
+        [terminal_productions]
+
+        Plus -> [Token::Plus];
+        Star -> [Token::Star];
+        Num -> ["Number"];
+    );
+    let mut grammar = statement();
+    grammar.import(&expression, "expr").unwrap();
+    let op = grammar
+        .symbols
+        .reverse_lookup(&Symbol::NONTERMINAL(String::from("expr::Op")))
+        .unwrap();
+    assert!(grammar.inline.contains(&op));
+    grammar
+        .merge_rule("Value", &expression, "Expr", "expr", Merge::Override)
+        .unwrap();
+
+    let parser = LR1_Parser::new(grammar);
+    assert!(!parser.conflicts);
+    assert!(parser
+        .grammar
+        .productions
+        .vec
+        .iter()
+        .all(|production| production.head != op && !production.body.contains(&op)));
+    let mut errors = Vec::new();
+    parser
+        .parse(Token::lexer("< 1 + 2 * 3"), &mut errors, &mut AST::new())
+        .unwrap();
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn import_templates() {
+    let lists: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> Comma<Num>;
+
+        Comma<T> -> T
+        | Comma<T> Plus T;
+
+        [terminal_productions]
+
+        Plus -> [Token::Plus];
+        Num -> ["Number"];
+    );
+    let mut grammar = statement();
+    grammar.import(&lists, "lists").unwrap();
+    //the template's own symbols are the imported ones
+    let list = grammar
+        .instantiate("lists::Comma", &["lists::Num"])
+        .unwrap();
+    let lookup = |name: &str| {
+        grammar
+            .symbols
+            .reverse_lookup(&Symbol::NONTERMINAL(name.to_string()))
+            .unwrap()
+    };
+    let (plus, num) = (lookup("lists::Plus"), lookup("lists::Num"));
+    let bodies: Vec<&Vec<SymbolId>> = grammar
+        .productions
+        .vec
+        .iter()
+        .filter(|production| production.head == list)
+        .map(|production| &production.body)
+        .collect();
+    assert_eq!(bodies, [&vec![num], &vec![list, plus, num]]);
+
+    let Err(GrammarError::ImportConflicts(conflicts)) = grammar.import(&lists, "lists") else {
+        panic!("importing twice conflicts");
+    };
+    assert!(conflicts.contains(&String::from("template lists::Comma is already defined")));
+}
@@ -0,0 +1,33 @@
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack, AST};
+
+#[test]
+fn error_message() {
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> E;
+
+        E -> A B;
+
+        [terminal_productions]
+
+        A -> [Token::A] ! "expected 'b' after 'a'";
+
+        B -> [Token::B];
+    );
+    assert_eq!(
+        grammar.productions.vec[3].error_message.as_deref(),
+        Some("expected 'b' after 'a'")
+    );
+    let parser = LR1_Parser::new(grammar);
+    let mut errors = Vec::new();
+    let mut ast = AST::new();
+    parser
+        .parse(Token::lexer("a a"), &mut errors, &mut ast)
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message(), "expected 'b' after 'a'");
+    assert_eq!(errors[0].span, 2..3);
+}
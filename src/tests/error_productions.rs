@@ -0,0 +1,162 @@
+use logos::Logos;
+
+use crate::grammar;
+use crate::outcome::ParseStats;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack};
+
+type Log = Vec<String>;
+
+fn statement(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let entry = format!("statement {:?}", ctx.span());
+    ctx.ast().push(entry);
+}
+
+fn error_statement(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    //the error terminal carries the token the error was detected on
+    let entry = format!("error {:?} at {}", ctx.span(), ctx.tokens()[0].0);
+    ctx.ast().push(entry);
+}
+
+fn statements() -> Grammar<Log, Token, TranslatorStack> {
+    grammar!(
+        Start -> Stmts;
+
+        Stmts -> Stmts Stmt
+        | Stmt;
+
+        Stmt -> Expr Semi { |ctx| statement(ctx) }
+        | error Semi { |ctx| error_statement(ctx) };
+
+        Expr -> Expr Plus Num
+        | Num;
+
+        [terminal_productions]
+
+        Plus -> [Token::A];
+        Semi -> [Token::B];
+        Num -> ["Number"];
+    )
+}
+
+#[test]
+fn error_productions() {
+    let parser = LR1_Parser::new(statements());
+
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let value = parser.parse(
+        Token::lexer("1 a 2 b 3 a a 4 b 5 b 6 6 b 7 b"),
+        &mut errors,
+        &mut log,
+    );
+    //error productions keep the parse whole, so it still has a value
+    let outcome = value.unwrap();
+    assert!(outcome.recovered);
+    assert!(outcome.value.is_some());
+    assert_eq!(
+        outcome.stats,
+        ParseStats {
+            tokens: 13,
+            reductions: 27,
+            recoveries: 2,
+            discarded: 3,
+        }
+    );
+    let errors: Vec<(logos::Span, String)> = errors
+        .into_iter()
+        .map(|error| (error.span.clone(), error.message()))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            (12..13, String::from("Expected Number")),
+            (24..25, String::from("Expected B or A")),
+        ]
+    );
+    //the error terminal spans the popped symbols and the discarded input
+    assert_eq!(
+        log,
+        [
+            "statement 0..7",
+            "error 8..17 at A",
+            "statement 18..21",
+            "error 22..27 at Number",
+            "statement 28..31",
+        ]
+    );
+}
+
+#[test]
+fn unrecoverable_error() {
+    let parser = LR1_Parser::new(statements());
+
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    //no Semi follows the error, input is discarded up to the end
+    let failure = parser
+        .parse(Token::lexer("1 b 2 a a 3"), &mut errors, &mut log)
+        .unwrap_err();
+    assert_eq!(failure.stats.discarded, 2);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(log, ["statement 0..3"]);
+}
+
+#[test]
+fn builder_error_productions() {
+    let grammar = GrammarBuilder::<Log, Token, TranslatorStack>::new()
+        .terminal(Token::A)
+        .terminal(Token::B)
+        .rule("Start")
+        .seq(["Stmts"])
+        .rule("Stmts")
+        .seq(["Stmts", "Stmt"])
+        .seq(["Stmt"])
+        .rule("Stmt")
+        .seq(["A", "B"])
+        .action(statement)
+        .seq(["error", "B"])
+        .action(error_statement)
+        .build()
+        .unwrap();
+    let parser = LR1_Parser::new(grammar);
+
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    parser
+        .parse(Token::lexer("a b a a a b a b"), &mut errors, &mut log)
+        .unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        log,
+        ["statement 0..3", "error 4..11 at A", "statement 12..15"]
+    );
+}
+
+#[test]
+fn typed_error_productions() {
+    //the value of the error terminal is the token the error was detected on
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> Stmts => |stmts: Vec<String>| -> Vec<String> { stmts };
+
+        Stmts -> Stmts Stmt => |stmts: Vec<String>, stmt: String| -> Vec<String> { [stmts, vec![stmt]].concat() }
+        | Stmt => |stmt: String| -> Vec<String> { vec![stmt] };
+
+        Stmt -> Num Semi => |_n: (), _s: ()| -> String { String::from("statement") }
+        | error Semi => |e: Token, _s: ()| -> String { format!("error at {e}") };
+
+        [terminal_productions]
+
+        Semi -> [Token::B];
+        Num -> ["Number"];
+    );
+    let parser = LR1_Parser::new(grammar);
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let outcome = parser
+        .parse(Token::lexer("a b 2 b"), &mut errors, &mut log)
+        .unwrap();
+    let value = outcome.value.unwrap().downcast::<Vec<String>>().unwrap();
+    assert_eq!(*value, ["error at A", "statement"]);
+    assert_eq!(errors.len(), 1);
+}
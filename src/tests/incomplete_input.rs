@@ -0,0 +1,114 @@
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack, AST};
+
+type Log = Vec<String>;
+
+fn reduced(ctx: &mut ActionContext<Log, Token, TranslatorStack>, head: &str) {
+    let entry = format!("{head} {:?}", ctx.span());
+    ctx.ast().push(entry);
+}
+
+fn logged() -> LR1_Parser<Log, Token, TranslatorStack> {
+    LR1_Parser::new(grammar!(
+        Start -> E;
+
+        E -> E Plus T { |ctx| reduced(ctx, "E") }
+        | T { |ctx| reduced(ctx, "E") };
+
+        T -> LParen E RParen { |ctx| reduced(ctx, "T") }
+        | Num { |ctx| reduced(ctx, "T") };
+
+        [terminal_productions]
+
+        Plus -> [Token::A];
+        LParen -> [Token::C];
+        RParen -> [Token::D];
+        Num -> ["Number"];
+    ))
+}
+
+fn parser() -> LR1_Parser<AST, Token, TranslatorStack> {
+    LR1_Parser::new(grammar!(
+        Start -> E;
+
+        E -> E Plus T
+        | T;
+
+        T -> LParen E RParen
+        | Num;
+
+        [terminal_productions]
+
+        Plus -> [Token::A];
+        LParen -> [Token::C];
+        RParen -> [Token::D];
+        Num -> ["Number"];
+    ))
+}
+
+fn status(input: &str) -> (InputStatus, Vec<ParseError>) {
+    let mut errors = Vec::new();
+    let status = parser().parse_interactive(Token::lexer(input), &mut errors, &mut AST::new());
+    (status, errors)
+}
+
+#[test]
+fn accepted_input() {
+    let (status, errors) = status("1 a c 2 a 3 d");
+    assert!(matches!(status, InputStatus::Accepted(_)));
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn incomplete_input() {
+    for input in ["", "c 1 a", "c 1 a 2", "1 a c c 2 d"] {
+        let (status, errors) = status(input);
+        assert!(
+            matches!(status, InputStatus::Incomplete(_)),
+            "{input:?} is {status:?}"
+        );
+        assert!(errors.is_empty());
+    }
+}
+
+#[test]
+fn invalid_input() {
+    //errors before the end of the input are genuine, even if it ends early
+    for input in ["1 a a 2", "1 d", "1 d c 1 a", "c 1 2"] {
+        let (status, errors) = status(input);
+        assert!(
+            matches!(status, InputStatus::Invalid(_)),
+            "{input:?} is {status:?}"
+        );
+        assert!(!errors.is_empty());
+    }
+}
+
+#[test]
+fn parse_reports_incomplete_input() {
+    //outside of parse_interactive the end of the input is a syntax error
+    let mut errors = Vec::new();
+    let outcome = parser().parse(Token::lexer("c 1 a"), &mut errors, &mut AST::new());
+    assert!(outcome.is_err());
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn incomplete_input_runs_no_action_on_eof() {
+    //`2` and `c 1 a 2` would be reduced by the end of the input
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let status = logged().parse_interactive(Token::lexer("c 1 a 2"), &mut errors, &mut log);
+    assert!(matches!(status, InputStatus::Incomplete(_)));
+    assert_eq!(log, ["T 2..3", "E 2..3"]);
+
+    //a complete input is reduced to the end
+    let mut log = Log::new();
+    let status = logged().parse_interactive(Token::lexer("c 1 d"), &mut errors, &mut log);
+    assert!(matches!(status, InputStatus::Accepted(_)));
+    assert_eq!(log, ["T 2..3", "E 2..3", "T 0..5", "E 0..5"]);
+    assert!(errors.is_empty());
+}
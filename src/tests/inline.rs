@@ -0,0 +1,101 @@
+use logos::Logos;
+
+use crate::error::GrammarError;
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack};
+
+type Log = Vec<String>;
+
+fn record(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let entry = format!(
+        "{:?} {:?} {:?}",
+        ctx.tokens().iter().collect::<Vec<_>>(),
+        ctx.spans(),
+        ctx.span()
+    );
+    ctx.ast().push(entry);
+}
+
+fn number(token: Token) -> i64 {
+    match token {
+        Token::Number(number) => number,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn inline() {
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> E => |e: i64| -> i64 { e };
+
+        E -> E Op Num { |ctx| record(ctx) } => |l: i64, op: i64, r: i64| -> i64 { l + op * r }
+        | Num => |n: i64| -> i64 { n };
+
+        #[inline]
+        Op -> Plus { |ctx| record(ctx) } => |_plus: Token| -> i64 { 1 }
+        | Minus => |_minus: Token| -> i64 { -1 };
+
+        [terminal_productions]
+
+        Num -> ["Number"] => |token: Token| -> i64 { number(token) };
+
+        #[inline]
+        Plus -> [Token::A] { |ctx| record(ctx) } => |token: Token| -> Token { token };
+
+        #[inline]
+        Minus -> [Token::B] => |token: Token| -> Token { token };
+    );
+    let parser = LR1_Parser::new(grammar);
+    assert!(!parser.conflicts);
+    let op = parser
+        .grammar
+        .symbols
+        .reverse_lookup(&Symbol::NONTERMINAL(String::from("Op")))
+        .unwrap();
+    assert!(parser
+        .grammar
+        .productions
+        .vec
+        .iter()
+        .all(|production| production.head != op && !production.body.contains(&op)));
+
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let value = parser.parse(Token::lexer("1 a 2 b 5"), &mut errors, &mut log);
+    assert!(errors.is_empty());
+    assert_eq!(
+        *value.unwrap().value.unwrap().downcast::<i64>().unwrap(),
+        -2
+    );
+    assert_eq!(
+        log,
+        [
+            "[(A, 2..3)] [2..3] 2..3",
+            "[] [2..3] 2..3",
+            "[] [0..1, 2..3, 4..5] 0..5",
+            "[] [0..5, 6..7, 8..9] 0..9",
+        ]
+    );
+}
+
+#[test]
+fn recursive_inline() {
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> E;
+
+        #[inline]
+        E -> E A
+        | A;
+
+        [terminal_productions]
+
+        A -> [Token::A];
+    );
+    assert_eq!(
+        LR1_Parser::try_new(grammar).err(),
+        Some(GrammarError::InvalidInline(String::from(
+            "inline nonterminal E is recursive"
+        )))
+    );
+}
@@ -0,0 +1,160 @@
+use std::cell::Cell;
+
+use logos::{Lexer, Logos};
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::TranslatorStack;
+use crate::token::TokenKind;
+
+//words are type names once a typedef registered them in the lexer's extras
+#[derive(Logos, TokenKind, Clone, Debug, PartialEq)]
+#[logos(skip r"[ \t\n\r]+")]
+#[logos(extras = Vec<String>)]
+enum Token {
+    #[token("type")]
+    Type,
+    #[token(";")]
+    Semi,
+    #[regex("[a-z]+", word)]
+    Word,
+    Ident(String),
+    TypeName(String),
+    #[token_kind(error)]
+    Error,
+    #[token_kind(eof)]
+    Eof,
+}
+
+thread_local! {
+    //words lexed by the test's thread
+    static WORDS: Cell<usize> = const { Cell::new(0) };
+}
+
+fn word(lexer: &mut Lexer<Token>) -> Token {
+    WORDS.set(WORDS.get() + 1);
+    let word = lexer.slice().to_string();
+    if lexer.extras.contains(&word) {
+        Token::TypeName(word)
+    } else {
+        Token::Ident(word)
+    }
+}
+
+type Log = Vec<String>;
+
+fn name(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    if let Some((Token::Ident(name), _)) = ctx.tokens().first() {
+        let name = name.clone();
+        ctx.ast().push(name);
+    }
+}
+
+fn typedef(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let name = ctx.ast().pop().unwrap();
+    assert!(ctx.extras::<String>().is_none());
+    match ctx.extras::<Vec<String>>() {
+        Some(types) => {
+            types.push(name);
+            //the word after `;` is already lexed
+            assert!(ctx.relex_lookahead());
+        }
+        //pushed tokens come from no lexer
+        None => {
+            assert!(!ctx.relex_lookahead());
+            ctx.ast().push(format!("unregistered {name}"));
+        }
+    }
+}
+
+fn declaration(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let entry = format!("declaration {:?}", ctx.span());
+    ctx.ast().push(entry);
+}
+
+fn expression(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let entry = format!("expression {:?}", ctx.span());
+    ctx.ast().push(entry);
+}
+
+fn typedefs() -> LR1_Parser<Log, Token, TranslatorStack> {
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> Stmts;
+
+        Stmts -> Stmts Stmt
+        | Stmt;
+
+        Stmt -> Typedef
+        | Declaration
+        | Expression;
+
+        Typedef -> TypeKw Name Semicolon { |ctx| typedef(ctx) }
+        | TypeKw Name error Semicolon { |ctx| typedef(ctx) };
+
+        Declaration -> Type Name Semicolon { |ctx| declaration(ctx) };
+
+        Expression -> Name Semicolon { |ctx| expression(ctx) };
+
+        [terminal_productions]
+
+        TypeKw -> [Token::Type];
+        Semicolon -> [Token::Semi];
+        Name -> ["Ident"] { |ctx| name(ctx) };
+        Type -> ["TypeName"];
+    );
+    LR1_Parser::new(grammar)
+}
+
+#[test]
+fn lexer_hack() {
+    let parser = typedefs();
+    assert!(!parser.conflicts);
+
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let lexer = Token::lexer("type foo; foo x; x; foo y;");
+    parser.parse(lexer, &mut errors, &mut log).unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(
+        log,
+        [
+            "x",
+            "declaration 10..16",
+            "x",
+            "expression 17..19",
+            "y",
+            "declaration 20..26",
+        ]
+    );
+}
+
+#[test]
+fn no_lexer_in_push_sessions() {
+    let parser = typedefs();
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let mut lexer = Token::lexer("type foo; x;");
+    let mut session = parser.session(&mut errors, &mut log);
+    while let Some(token) = lexer.next() {
+        session.push(token.unwrap(), lexer.span()).unwrap();
+    }
+    session.finish().unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(log, ["unregistered foo", "x", "expression 10..12"]);
+}
+
+#[test]
+fn relex_after_lookahead_for_repairs() {
+    let parser = typedefs();
+    assert!(!parser.conflicts);
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    //the repair of the second `;` reads `foo y ;` before the typedef is
+    //reduced with the error production
+    let lexer = Token::lexer("type foo; ; foo y;");
+    parser.parse(lexer, &mut errors, &mut log).unwrap();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(log, ["y", "declaration 12..18"]);
+    //the words read ahead are lexed again after the typedef, once
+    assert_eq!(WORDS.get(), 3 + 2);
+}
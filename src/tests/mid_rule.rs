@@ -0,0 +1,105 @@
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack};
+
+type Log = Vec<String>;
+
+fn open(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let entry = format!(
+        "open {:?} {:?} {:?}",
+        ctx.tokens().iter().collect::<Vec<_>>(),
+        ctx.spans(),
+        ctx.span()
+    );
+    ctx.ast().push(entry);
+}
+
+fn item(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let entry = format!("item {:?}", ctx.span());
+    ctx.ast().push(entry);
+}
+
+fn close(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let entry = format!(
+        "close {:?} {:?}",
+        ctx.tokens().iter().collect::<Vec<_>>(),
+        ctx.spans()
+    );
+    ctx.ast().push(entry);
+}
+
+fn number(token: Token) -> i64 {
+    match token {
+        Token::Number(number) => number,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn mid_rule() {
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> Block;
+
+        Block -> Open { |ctx| open(ctx) } Items Close { |ctx| close(ctx) };
+
+        Items -> Items Item
+        | Item;
+
+        Item -> Num { |ctx| item(ctx) }
+        | Block;
+
+        [terminal_productions]
+
+        Open -> [Token::A];
+        Close -> [Token::B];
+        Num -> ["Number"];
+    );
+    let parser = LR1_Parser::new(grammar);
+    assert!(!parser.conflicts);
+
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    parser
+        .parse(Token::lexer("a 1 a 2 b b"), &mut errors, &mut log)
+        .unwrap();
+    assert!(errors.is_empty());
+    //nested blocks are opened before their items are reduced
+    assert_eq!(
+        log,
+        [
+            "open [] [0..1] 0..1",
+            "item 2..3",
+            "open [] [4..5] 4..5",
+            "item 6..7",
+            "close [] [4..5, 5..5, 6..7, 8..9]",
+            "close [] [0..1, 1..1, 2..9, 10..11]",
+        ]
+    );
+}
+
+#[test]
+fn mid_rule_values() {
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> Pair => |pair: i64| -> i64 { pair };
+
+        //the mid-rule action counts as a symbol whose value is ()
+        Pair -> Num { |ctx| open(ctx) } Num => |l: i64, _mid: (), r: i64| -> i64 { l * 10 + r };
+
+        [terminal_productions]
+
+        Num -> [Token::Number(0)] => |token: Token| -> i64 { number(token) };
+    );
+    let parser = LR1_Parser::new(grammar);
+
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let value = parser.parse(Token::lexer("4 2"), &mut errors, &mut log);
+    assert!(errors.is_empty());
+    assert_eq!(
+        *value.unwrap().value.unwrap().downcast::<i64>().unwrap(),
+        42
+    );
+    assert_eq!(log, ["open [] [0..1] 0..1"]);
+}
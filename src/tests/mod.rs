@@ -2,7 +2,30 @@
 // mod E1;
 // mod basic;
 // mod left_factor;
-// mod tokens;
+mod bison;
+mod builder;
+mod codegen;
+mod compose;
+mod error_message;
+mod error_productions;
+mod incomplete_input;
+mod inline;
+mod lexer_hack;
+mod mid_rule;
+mod parse_error;
+mod predicates;
+mod production_tokens;
+mod push_parser;
+mod recovery;
+mod repair;
+mod shared_parser;
+mod templates;
+mod terminal_check;
+mod terminal_id;
+mod token_derive;
+mod token_source;
+mod tokens;
+mod typed_values;
 
 #[derive(Debug, Clone, PartialEq)]
 struct AST {
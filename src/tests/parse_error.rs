@@ -0,0 +1,119 @@
+use logos::Logos;
+
+use crate::error::ParseErrorKind;
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{TranslatorStack, AST};
+use crate::token::TokenKind;
+
+#[derive(Logos, TokenKind, Clone, Debug, PartialEq)]
+#[logos(skip r"[ \t\n\r]+")]
+enum Token {
+    #[token("+")]
+    #[display = "'+'"]
+    Plus,
+    #[regex("[0-9]+", |lex| lex.slice().parse().ok())]
+    #[display = "a number"]
+    Number(i64),
+    //never used by the grammar
+    #[token("*")]
+    Star,
+    #[token("?")]
+    #[token_kind(error)]
+    Error,
+    #[token_kind(eof)]
+    Eof,
+}
+
+fn parse(input: &str) -> Vec<ParseError> {
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> E;
+
+        E -> E Plus Number
+        | Number;
+
+        [terminal_productions]
+
+        Number -> ["Number"];
+
+        Plus -> ["Plus"];
+    );
+    let parser = LR1_Parser::new(grammar);
+    let mut errors = Vec::new();
+    //accepted or not, only the errors matter
+    let _ = parser.parse(Token::lexer(input), &mut errors, &mut AST::new());
+    errors
+}
+
+#[test]
+fn syntax_error() {
+    let errors = parse("1 + +");
+    assert_eq!(errors.len(), 1);
+    let error = &errors[0];
+    assert_eq!(error.kind, ParseErrorKind::Syntax);
+    assert_eq!(error.span, 4..5);
+    assert_eq!(error.found.as_deref(), Some("'+'"));
+    assert_eq!(error.terminal_id, Some(0));
+    assert_eq!(error.expected, ["a number"]);
+    assert!(error.state.is_some());
+    assert_eq!(error.items.len(), 1);
+    assert_eq!(error.message(), "Expected a number");
+    assert_eq!(error.to_string(), error.message());
+}
+
+#[test]
+fn unexpected_eof() {
+    let errors = parse("1 +");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedEof);
+    assert_eq!(errors[0].found.as_deref(), Some("EOF"));
+    assert_eq!(errors[0].terminal_id, None);
+    assert!(errors[0].production_end);
+}
+
+#[test]
+fn lexical_error() {
+    let errors = parse("1 ? 2");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ParseErrorKind::Lexical);
+    assert_eq!(errors[0].span, 2..3);
+    assert_eq!(errors[0].found.as_deref(), Some("\"?\""));
+    assert_eq!(errors[0].message(), "Invalid token \"?\"");
+}
+
+#[test]
+fn unmatched_input() {
+    //the lexer matches nothing at @, the error is reported and recovered from
+    let errors = parse("1 + @ 2 + 3 + 4 + + 5");
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].kind, ParseErrorKind::Lexical);
+    assert_eq!(errors[0].span, 4..5);
+    assert_eq!(errors[0].message(), "Invalid token \"@\"");
+    assert_eq!(errors[1].kind, ParseErrorKind::Syntax);
+    assert_eq!(errors[1].span, 18..19);
+}
+
+#[test]
+fn unknown_terminal() {
+    let errors = parse("1 * 2");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ParseErrorKind::Syntax);
+    assert_eq!(errors[0].span, 2..3);
+    assert_eq!(errors[0].found.as_deref(), Some("Star"));
+    assert_eq!(errors[0].message(), "Expected EOF or '+'");
+}
+
+#[test]
+fn empty_input() {
+    let errors = parse("");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedEof);
+    assert_eq!(errors[0].message(), "Expected a number");
+}
+
+#[test]
+fn semantic_error() {
+    let error = ParseError::new(0..3, String::from("undefined variable x"));
+    assert_eq!(error.kind, ParseErrorKind::Semantic);
+    assert_eq!(error.message(), "undefined variable x");
+}
@@ -0,0 +1,110 @@
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack};
+
+//`a 1` declares 1 as a type name, `1 2` declares 2 with type 1, `3` uses 3
+#[derive(Debug, Clone, PartialEq, Default)]
+struct Scope {
+    names: Vec<i64>,
+    types: Vec<i64>,
+    log: Vec<String>,
+}
+
+fn name(ctx: &mut ActionContext<Scope, Token, TranslatorStack>) {
+    if let Some((Token::Number(number), _)) = ctx.tokens().first() {
+        let number = *number;
+        ctx.ast().names.push(number);
+    }
+}
+
+fn typedef(ctx: &mut ActionContext<Scope, Token, TranslatorStack>) {
+    let name = ctx.ast().names.pop().unwrap();
+    ctx.ast().types.push(name);
+}
+
+fn declaration(ctx: &mut ActionContext<Scope, Token, TranslatorStack>) {
+    let entry = format!("declaration {:?}", ctx.span());
+    ctx.ast().log.push(entry);
+}
+
+fn usage(ctx: &mut ActionContext<Scope, Token, TranslatorStack>) {
+    let entry = format!("use {:?}", ctx.span());
+    ctx.ast().log.push(entry);
+}
+
+fn is_type(ctx: &PredicateContext<Scope, Token>) -> bool {
+    matches!(ctx.value::<Token>(0), Some(Token::Number(number)) if ctx.ast().types.contains(number))
+}
+
+#[test]
+fn predicates() {
+    let grammar: Grammar<Scope, Token, TranslatorStack> = grammar!(
+        Start -> Stmts;
+
+        Stmts -> Stmts Stmt
+        | Stmt;
+
+        Stmt -> Typedef
+        | Declaration
+        | Use;
+
+        Typedef -> Typ Name { |ctx| typedef(ctx) };
+
+        Declaration -> TypeName Name { |ctx| declaration(ctx) };
+
+        Use -> Name { |ctx| usage(ctx) };
+
+        TypeName -> ?{ |ctx| is_type(ctx) } Name;
+
+        [terminal_productions]
+
+        Typ -> [Token::A];
+
+        Name -> ["Number"] { |ctx| name(ctx) } => |token: Token| -> Token { token };
+    );
+    let parser = LR1_Parser::new(grammar);
+    //TypeName -> Name and Use -> Name are decided by the predicate
+    assert!(!parser.conflicts);
+
+    let mut errors = Vec::new();
+    let mut scope = Scope::default();
+    parser
+        .parse(Token::lexer("a 1 1 2 2 3"), &mut errors, &mut scope)
+        .unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(scope.log, ["declaration 4..7", "use 8..9", "use 10..11"]);
+}
+
+#[test]
+fn failed_predicate() {
+    let grammar: Grammar<Scope, Token, TranslatorStack> = grammar!(
+        Start -> Declaration;
+
+        Declaration -> TypeName Name { |ctx| declaration(ctx) };
+
+        TypeName -> ?{ |ctx| is_type(ctx) } Name;
+
+        [terminal_productions]
+
+        Name -> ["Number"] => |token: Token| -> Token { token };
+    );
+    let parser = LR1_Parser::new(grammar);
+
+    let mut errors = Vec::new();
+    let mut scope = Scope::default();
+    scope.types.push(1);
+    parser
+        .parse(Token::lexer("1 2"), &mut errors, &mut scope)
+        .unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(scope.log, ["declaration 0..3"]);
+
+    //without an alternative a failed predicate is a syntax error
+    let mut scope = Scope::default();
+    let value = parser.parse(Token::lexer("1 2"), &mut errors, &mut scope);
+    assert!(value.unwrap().recovered);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span, 2..3);
+}
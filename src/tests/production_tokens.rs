@@ -0,0 +1,45 @@
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack};
+
+type Log = Vec<String>;
+
+fn record(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let tokens = ctx.tokens();
+    let entry = if tokens.is_empty() {
+        format!("{:?} {:?}", ctx.spans(), ctx.span())
+    } else {
+        format!("{:?}", tokens.iter().collect::<Vec<_>>())
+    };
+    ctx.ast().push(entry);
+}
+
+#[test]
+fn production_tokens() {
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> E;
+
+        E -> E P T { |ctx| record(ctx) }
+        | T;
+
+        [terminal_productions]
+
+        P -> [Token::C];
+
+        T -> [Token::A] { |ctx| record(ctx) }
+        | [Token::B] { |ctx| record(ctx) };
+    );
+    let parser = LR1_Parser::new(grammar);
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    parser
+        .parse(Token::lexer("a c  b"), &mut errors, &mut log)
+        .unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(
+        log,
+        vec!["[(A, 0..1)]", "[(B, 5..6)]", "[0..1, 2..3, 5..6] 0..6"]
+    );
+}
@@ -0,0 +1,170 @@
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack};
+
+type Log = Vec<String>;
+
+fn statement(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let entry = format!("statement {:?}", ctx.span());
+    ctx.ast().push(entry);
+}
+
+fn error_statement(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let entry = format!("error {:?}", ctx.span());
+    ctx.ast().push(entry);
+}
+
+fn statements() -> LR1_Parser<Log, Token, TranslatorStack> {
+    LR1_Parser::new(grammar!(
+        Start -> Stmts;
+
+        Stmts -> Stmts Stmt
+        | Stmt;
+
+        Stmt -> Expr Semi { |ctx| statement(ctx) }
+        | error Semi { |ctx| error_statement(ctx) };
+
+        Expr -> Expr Plus Num
+        | Num;
+
+        [terminal_productions]
+
+        Plus -> [Token::A];
+        Semi -> [Token::B];
+        Num -> ["Number"];
+    ))
+}
+
+//tokens of `input` with their spans, lexer errors left out
+fn tokens(input: &str) -> Vec<(Token, logos::Span)> {
+    let mut lexer = Token::lexer(input);
+    let mut tokens = vec![];
+    while let Some(token) = lexer.next() {
+        tokens.push((token.unwrap(), lexer.span()));
+    }
+    tokens
+}
+
+#[test]
+fn push_statuses() {
+    let parser = statements();
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let mut session = parser.session(&mut errors, &mut log);
+    let statuses: Vec<PushStatus> = tokens("1 a 2 b 3 b")
+        .into_iter()
+        .map(|(token, span)| session.push(token, span).unwrap())
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            PushStatus::Incomplete,
+            PushStatus::Incomplete,
+            PushStatus::Incomplete,
+            PushStatus::Complete,
+            PushStatus::Incomplete,
+            PushStatus::Complete,
+        ]
+    );
+    let outcome = session.finish().unwrap();
+    assert!(!outcome.recovered);
+    assert_eq!(outcome.stats.tokens, 6);
+    assert!(errors.is_empty());
+    assert_eq!(log, ["statement 0..7", "statement 8..11"]);
+}
+
+#[test]
+fn push_errors() {
+    let parser = statements();
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let mut session = parser.session(&mut errors, &mut log);
+    let statuses: Vec<PushStatus> = tokens("1 a a 4 b 5 b")
+        .into_iter()
+        .map(|(token, span)| session.push(token, span).unwrap())
+        .collect();
+    assert_eq!(
+        statuses,
+        [
+            PushStatus::Incomplete,
+            PushStatus::Incomplete,
+            //syntax error, then discarded until Semi
+            PushStatus::Error,
+            PushStatus::Recovering,
+            PushStatus::Complete,
+            PushStatus::Incomplete,
+            PushStatus::Complete,
+        ]
+    );
+    let outcome = session.finish().unwrap();
+    assert!(outcome.recovered);
+    assert_eq!(outcome.stats.discarded, 2);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].span, 4..5);
+    assert_eq!(log, ["error 0..9", "statement 10..13"]);
+}
+
+#[test]
+fn push_unfinished_input() {
+    let parser = statements();
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let mut session = parser.session(&mut errors, &mut log);
+    for (token, span) in tokens("1 b 2 a") {
+        session.push(token, span).unwrap();
+    }
+    let failure = session.finish().unwrap_err();
+    assert_eq!(failure.span, 7..7);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message(), "Expected Number");
+}
+
+#[test]
+fn push_matches_parse() {
+    let input = "1 a 2 b 3 3 b a b 4 a 5 b";
+    let parser = statements();
+
+    let (mut parse_errors, mut parse_log) = (Vec::new(), Log::new());
+    let parsed = parser
+        .parse(Token::lexer(input), &mut parse_errors, &mut parse_log)
+        .unwrap();
+
+    let (mut push_errors, mut push_log) = (Vec::new(), Log::new());
+    let mut session = parser.session(&mut push_errors, &mut push_log);
+    for (token, span) in tokens(input) {
+        session.push(token, span).unwrap();
+    }
+    let pushed = session.finish().unwrap();
+
+    assert_eq!(parsed.stats, pushed.stats);
+    assert_eq!(parse_log, push_log);
+    assert_eq!(parse_errors.len(), push_errors.len());
+    for (parsed, pushed) in parse_errors.iter().zip(push_errors.iter()) {
+        assert_eq!(parsed.span, pushed.span);
+        assert_eq!(parsed.message(), pushed.message());
+    }
+}
+
+#[test]
+fn push_after_failure() {
+    let parser = statements();
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let mut session = parser.session(&mut errors, &mut log);
+    assert_eq!(
+        session.push(Token::Number(1), 0..1).unwrap(),
+        PushStatus::Incomplete
+    );
+    //the end of the input pushed as a token can't be recovered from
+    let failure = session.push(Token::Eof, 1..1).unwrap_err();
+    assert_eq!(failure.span, 1..1);
+    //the session stays failed, the tokens after are not parsed
+    let again = session.push(Token::Number(1), 2..3).unwrap_err();
+    assert_eq!(again.span, failure.span);
+    assert_eq!(again.stats, failure.stats);
+    let finished = session.finish().unwrap_err();
+    assert_eq!(finished.span, failure.span);
+    assert_eq!(errors.len(), 1);
+}
@@ -0,0 +1,162 @@
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack};
+
+type Log = Vec<String>;
+
+fn statement(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let entry = format!("statement {:?}", ctx.span());
+    ctx.ast().push(entry);
+}
+
+fn statements() -> Grammar<Log, Token, TranslatorStack> {
+    grammar!(
+        Start -> Stmts;
+
+        Stmts -> Stmts Stmt
+        | Stmt;
+
+        Stmt -> Expr Semi { |ctx| statement(ctx) };
+
+        Expr -> Expr Plus Num
+        | Num;
+
+        [terminal_productions]
+
+        Plus -> [Token::A];
+        Semi -> [Token::B];
+        Num -> ["Number"];
+    )
+}
+
+#[test]
+fn recovery() {
+    let parser = LR1_Parser::new(statements());
+
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let value = parser.parse(
+        Token::lexer("1 a 2 b 3 a a 4 b 5 b 6 6 b 7 b"),
+        &mut errors,
+        &mut log,
+    );
+    //values are incomplete after panic mode
+    let outcome = value.unwrap();
+    assert!(outcome.recovered);
+    assert!(outcome.value.is_none());
+    let errors: Vec<(logos::Span, String)> = errors
+        .into_iter()
+        .map(|error| (error.span.clone(), error.message()))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            (12..13, String::from("Expected Number")),
+            (24..25, String::from("Expected B or A")),
+        ]
+    );
+    //statements after an error are still parsed
+    assert_eq!(
+        log,
+        [
+            "statement 0..7",
+            "statement 8..17",
+            "statement 18..21",
+            "statement 22..27",
+            "statement 28..31",
+        ]
+    );
+}
+
+#[test]
+fn cascading_errors() {
+    let parser = LR1_Parser::new(statements());
+
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    parser
+        .parse(Token::lexer("1 a b 2 b b 3 b"), &mut errors, &mut log)
+        .unwrap();
+    //the second b comes two tokens after a recovery, it is reported as
+    //cascaded
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].span, 4..5);
+    assert!(!errors[0].cascaded);
+    assert_eq!(errors[1].span, 10..11);
+    assert!(errors[1].cascaded);
+    assert_eq!(log, ["statement 0..11", "statement 12..15"]);
+}
+
+#[test]
+fn error_on_first_token() {
+    let parser = LR1_Parser::new(statements());
+
+    //no state below the first token can recover, it is dropped and the
+    //end of the input ends the parse instead of being dropped forever
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let failure = parser
+        .parse(Token::lexer("b"), &mut errors, &mut log)
+        .unwrap_err();
+    assert_eq!(failure.span, 1..1);
+    assert_eq!(errors.len(), 2);
+    assert!(errors[1].cascaded);
+
+    //so does finishing a push session
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let mut session = parser.session(&mut errors, &mut log);
+    assert_eq!(session.push(Token::B, 0..1).unwrap(), PushStatus::Error);
+    assert!(session.finish().is_err());
+
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let outcome = parser
+        .parse(Token::lexer("b 1 b"), &mut errors, &mut log)
+        .unwrap();
+    assert!(outcome.recovered);
+    assert_eq!(outcome.stats.discarded, 1);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(log, ["statement 2..5"]);
+}
+
+thread_local! {
+    static STATEMENTS: std::cell::RefCell<Vec<i64>> = const { std::cell::RefCell::new(vec![]) };
+}
+
+#[test]
+fn typed_actions_after_recovery() {
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> Stmts => |count: usize| -> usize { count };
+
+        Stmts -> Stmts Stmt => |count: usize, _stmt: ()| -> usize { count + 1 }
+        | Stmt => |_stmt: ()| -> usize { 1 };
+
+        Stmt -> Num Semi => |num: i64, _semi: ()| -> () {
+            STATEMENTS.with(|statements| statements.borrow_mut().push(num))
+        };
+
+        [terminal_productions]
+
+        Semi -> [Token::B];
+        Num -> ["Number"] => |token: Token| -> i64 {
+            match token {
+                Token::Number(number) => number,
+                _ => unreachable!(),
+            }
+        };
+    );
+    let parser = LR1_Parser::new(grammar);
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let outcome = parser
+        .parse(Token::lexer("1 b 2 a b 3 b 4 b"), &mut errors, &mut log)
+        .unwrap();
+    assert_eq!(errors.len(), 1);
+    //the statements around the recovered one keep their values, the ones
+    //it is part of have none
+    STATEMENTS.with(|statements| assert_eq!(*statements.borrow(), [1, 3, 4]));
+    assert!(outcome.value.is_none());
+}
@@ -0,0 +1,129 @@
+use logos::Logos;
+
+use crate::error::Repair;
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack};
+
+fn statements() -> Grammar<(), Token, TranslatorStack> {
+    grammar!(
+        Start -> Stmts;
+
+        Stmts -> Stmts Stmt
+        | Stmt;
+
+        Stmt -> Expr Semi;
+
+        Expr -> Expr Plus Num
+        | Num;
+
+        [terminal_productions]
+
+        Plus -> [Token::A];
+        Semi -> [Token::B];
+        Num -> ["Number"];
+    )
+}
+
+fn repairs(input: &str) -> Vec<Vec<Repair>> {
+    let parser = LR1_Parser::new(statements());
+    let mut errors = Vec::new();
+    //accepted or not, only the errors matter
+    let _ = parser.parse(Token::lexer(input), &mut errors, &mut ());
+    errors.into_iter().map(|error| error.repair).collect()
+}
+
+#[test]
+fn repair() {
+    assert_eq!(repairs("1 a a 2 b"), [vec![Repair::Delete { span: 4..5 }]]);
+    assert_eq!(
+        repairs("1 a b"),
+        [vec![Repair::Insert {
+            terminal: String::from("Number"),
+            at: 4
+        }]]
+    );
+    //neither deleting the first b nor inserting a number lets the second b parse
+    assert_eq!(
+        repairs("1 a b b"),
+        [vec![Repair::Replace {
+            span: 4..5,
+            terminal: String::from("Number")
+        }]]
+    );
+}
+
+#[test]
+fn repair_display() {
+    let repair = repairs("1 a 2 3 b");
+    assert_eq!(repair.len(), 1);
+    let repair: Vec<String> = repair[0].iter().map(|edit| edit.to_string()).collect();
+    assert_eq!(repair, ["delete 6..7"]);
+    assert_eq!(
+        Repair::Insert {
+            terminal: String::from(";"),
+            at: 42
+        }
+        .to_string(),
+        "insert `;` at 42..42"
+    );
+}
+
+fn repaired(input: &str) -> (Vec<Vec<Repair>>, Option<ParseOutcome>) {
+    let mut parser = LR1_Parser::new(statements());
+    parser.repair_input = true;
+    let mut errors = Vec::new();
+    let outcome = parser.parse(Token::lexer(input), &mut errors, &mut ()).ok();
+    (
+        errors.into_iter().map(|error| error.repair).collect(),
+        outcome,
+    )
+}
+
+#[test]
+fn repair_input() {
+    //the repair replaces the recovery, the values are complete
+    let (repair, outcome) = repaired("1 a a 2 b 3 b");
+    assert_eq!(repair, [vec![Repair::Delete { span: 4..5 }]]);
+    let outcome = outcome.unwrap();
+    assert!(outcome.value.is_some());
+    assert_eq!(outcome.stats.discarded, 1);
+    let (repair, outcome) = repaired("1 b 2");
+    assert_eq!(
+        repair,
+        [vec![Repair::Insert {
+            terminal: String::from("B"),
+            at: 5
+        }]]
+    );
+    assert_eq!(outcome.unwrap().stats.tokens, 4);
+    //a number can't be made up, the error is recovered from
+    let (repair, outcome) = repaired("1 a b");
+    assert_eq!(
+        repair,
+        [vec![Repair::Insert {
+            terminal: String::from("Number"),
+            at: 4
+        }]]
+    );
+    assert!(outcome.and_then(|outcome| outcome.value).is_none());
+    //errors after the repair are reported
+    let (repair, _) = repaired("1 a a 2 b 3 3 b");
+    assert_eq!(repair.len(), 2);
+}
+
+#[test]
+fn repair_pushed_input() {
+    let mut parser = LR1_Parser::new(statements());
+    parser.repair_input = true;
+    let mut errors = Vec::new();
+    let mut ast = ();
+    let mut session = parser.session(&mut errors, &mut ast);
+    session.push(Token::Number(1), 0..1).unwrap();
+    session.push(Token::Number(2), 2..3).unwrap();
+    session.push(Token::B, 4..5).unwrap();
+    let outcome = session.finish().unwrap();
+    assert!(outcome.value.is_some());
+    assert_eq!(outcome.stats.tokens, 4);
+    assert_eq!(errors.len(), 1);
+}
@@ -0,0 +1,93 @@
+use std::sync::OnceLock;
+
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack, AST};
+
+fn parser() -> &'static LR1_Parser<AST, Token, TranslatorStack> {
+    static PARSER: OnceLock<LR1_Parser<AST, Token, TranslatorStack>> = OnceLock::new();
+    PARSER.get_or_init(|| {
+        LR1_Parser::new(grammar!(
+            Start -> E => |e: String| -> String { e };
+
+            E -> E P T => |e: String, op: String, t: String| -> String { format!("({e}{op}{t})") }
+            | T => |t: String| -> String { t };
+
+            [terminal_productions]
+
+            P -> [Token::C] => |_c: Token| -> String { String::from("+") };
+
+            T -> [Token::A] => |a: Token| -> String { a.to_string() }
+            | [Token::B] => |b: Token| -> String { b.to_string() };
+        ))
+    })
+}
+
+fn parse(input: &str) -> Option<String> {
+    let mut errors = Vec::new();
+    let outcome = parser()
+        .parse(Token::lexer(input), &mut errors, &mut AST::new())
+        .ok()?;
+    Some(*outcome.value?.downcast::<String>().ok()?)
+}
+
+#[test]
+fn parser_is_send_and_sync() {
+    fn shareable<T: Send + Sync>() {}
+    shareable::<LR1_Parser<AST, Token, TranslatorStack>>();
+}
+
+#[test]
+fn concurrent_parses() {
+    let inputs = ["a c b", "b", "a c a c b", "b c b c b c a"];
+    let expected = ["(A+B)", "B", "((A+A)+B)", "(((B+B)+B)+A)"];
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..8)
+            .map(|thread| {
+                scope.spawn(move || {
+                    (0..50)
+                        .map(|n| parse(inputs[(thread + n) % inputs.len()]))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        for (thread, handle) in handles.into_iter().enumerate() {
+            for (n, value) in handle.join().unwrap().into_iter().enumerate() {
+                assert_eq!(
+                    value.as_deref(),
+                    Some(expected[(thread + n) % inputs.len()])
+                );
+            }
+        }
+    });
+}
+
+#[test]
+fn interleaved_sessions() {
+    let parser = parser();
+    let (mut first_errors, mut second_errors) = (Vec::new(), Vec::new());
+    let (mut first_ast, mut second_ast) = (AST::new(), AST::new());
+    let mut first = parser.session(&mut first_errors, &mut first_ast);
+    let mut second = parser.session(&mut second_errors, &mut second_ast);
+    for ((a, b), span) in [
+        (Token::A, Token::B),
+        (Token::C, Token::C),
+        (Token::B, Token::A),
+    ]
+    .into_iter()
+    .zip([0..1, 2..3, 4..5])
+    {
+        first.push(a, span.clone()).unwrap();
+        second.push(b, span).unwrap();
+    }
+    let first = first.finish().unwrap();
+    let second = second.finish().unwrap();
+    assert_eq!(*first.value.unwrap().downcast::<String>().unwrap(), "(A+B)");
+    assert_eq!(
+        *second.value.unwrap().downcast::<String>().unwrap(),
+        "(B+A)"
+    );
+    assert!(first_errors.is_empty() && second_errors.is_empty());
+}
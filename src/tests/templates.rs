@@ -0,0 +1,187 @@
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack};
+
+type Log = Vec<String>;
+
+fn item(ctx: &mut ActionContext<Log, Token, TranslatorStack>) {
+    let span = ctx.span();
+    ctx.ast().push(format!("{span:?}"));
+}
+
+#[test]
+fn templates() {
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> Comma<X> C Comma<Y>;
+
+        Comma<T> -> T { |ctx| item(ctx) }
+        | Comma<T> B T { |ctx| item(ctx) };
+
+        [terminal_productions]
+
+        X -> [Token::A];
+
+        Y -> [Token::D];
+
+        B -> [Token::B];
+
+        C -> [Token::C];
+    );
+    let comma_x = grammar
+        .symbols
+        .reverse_lookup(&Symbol::NONTERMINAL(String::from("Comma<X>")))
+        .unwrap();
+    let comma_y = grammar
+        .symbols
+        .reverse_lookup(&Symbol::NONTERMINAL(String::from("Comma<Y>")))
+        .unwrap();
+    let alternatives = |head: SymbolId| -> Vec<Production<Log, Token, TranslatorStack>> {
+        grammar
+            .productions
+            .vec
+            .iter()
+            .filter(|production| production.head == head)
+            .cloned()
+            .collect()
+    };
+    let (x, y) = (alternatives(comma_x), alternatives(comma_y));
+    assert_eq!(x.len(), 2);
+    assert_eq!(x[1].body[0], comma_x);
+    assert!(Arc::ptr_eq(
+        x[1].action.as_ref().unwrap(),
+        y[1].action.as_ref().unwrap()
+    ));
+
+    let parser = LR1_Parser::new(grammar);
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    parser
+        .parse(Token::lexer("a b a c d b d b d"), &mut errors, &mut log)
+        .unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(log, ["0..1", "0..5", "8..9", "8..13", "8..17"]);
+}
+
+#[test]
+fn typed_templates() {
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> Comma<X> C Comma<Y> => |xs: Vec<SemanticValue>, _c: (), ys: Vec<SemanticValue>| -> String {
+            let xs = xs.into_iter().map(|x| *x.downcast::<usize>().unwrap());
+            let ys = ys.into_iter().map(|y| *y.downcast::<String>().unwrap());
+            format!("{:?} {:?}", xs.collect::<Vec<usize>>(), ys.collect::<Vec<String>>())
+        };
+
+        Comma<T> -> T => |t: T| -> Vec<T> { vec![t] }
+        | Comma<T> B T => |list: Vec<T>, _b: (), t: T| -> Vec<T> {
+            let mut list = list;
+            list.push(t);
+            list
+        };
+
+        [terminal_productions]
+
+        X -> [Token::A] => |_a: Token| -> usize { 1 };
+
+        Y -> [Token::D] => |d: Token| -> String { d.to_string() };
+
+        B -> [Token::B];
+
+        C -> [Token::C];
+    );
+    let comma_x = grammar
+        .symbols
+        .reverse_lookup(&Symbol::NONTERMINAL(String::from("Comma<X>")))
+        .unwrap();
+    assert_eq!(grammar.value_types[&comma_x].name, "Vec<SemanticValue>");
+
+    let parser = LR1_Parser::new(grammar);
+    let mut errors = Vec::new();
+    let mut log = Log::new();
+    let outcome = parser
+        .parse(Token::lexer("a b a c d b d"), &mut errors, &mut log)
+        .unwrap();
+    let value = outcome.value.unwrap().downcast::<String>().unwrap();
+    assert_eq!(*value, "[1, 1] [\"D\", \"D\"]");
+}
+
+#[test]
+fn typed_template_mismatch() {
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> Comma<X> => |xs: Vec<usize>| -> usize { xs.len() };
+
+        Comma<T> -> T => |t: T| -> Vec<T> { vec![t] };
+
+        [terminal_productions]
+
+        X -> [Token::A] => |_a: Token| -> usize { 1 };
+    );
+    let error = LR1_Parser::try_new(grammar).unwrap_err();
+    assert_eq!(
+        error,
+        crate::error::GrammarError::MismatchedValueTypes(vec![String::from(
+            "argument 1 of the typed action of Start -> Comma<X> is Vec<usize> but Comma<X> has value type Vec<SemanticValue>"
+        )])
+    );
+}
+
+#[test]
+fn template_errors() {
+    //wrong number of arguments
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> Comma<X, X>;
+
+        Comma<T> -> T
+        | Comma<T> B T;
+
+        [terminal_productions]
+
+        X -> [Token::A];
+
+        B -> [Token::B];
+    );
+    let error = LR1_Parser::try_new(grammar).unwrap_err();
+    assert_eq!(
+        error,
+        crate::error::GrammarError::InvalidTemplate(String::from(
+            "template Comma takes 1 arguments but is given 2"
+        ))
+    );
+
+    //template that is not defined
+    let grammar: Grammar<Log, Token, TranslatorStack> = grammar!(
+        Start -> List<X>;
+
+        [terminal_productions]
+
+        X -> [Token::A];
+    );
+    let error = LR1_Parser::try_new(grammar).unwrap_err();
+    assert_eq!(error.to_string(), "template List is not defined");
+
+    //alternatives declaring different parameters
+    let mut grammar: Grammar<Log, Token, TranslatorStack> = Grammar::new();
+    let alternative = |body: &str| TemplateAlternative {
+        body: vec![(body.to_string(), vec![])],
+        error_message: None,
+        action: None,
+        action_tokens: quote::quote! {None}.into(),
+        value_action: None,
+        value_action_tokens: quote::quote! {None}.into(),
+        argument_types: None,
+        value_type: None,
+        predicate: None,
+        predicate_tokens: quote::quote! {None}.into(),
+    };
+    grammar
+        .define_template("Pair", &["T", "U"], alternative("T"))
+        .unwrap();
+    let error = grammar
+        .define_template("Pair", &["T"], alternative("T"))
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "template Pair is declared with parameters T, U and T"
+    );
+}
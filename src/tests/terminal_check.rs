@@ -0,0 +1,97 @@
+use crate::error::GrammarError;
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack, AST};
+use crate::token::TokenKind;
+
+#[test]
+fn unknown_terminal() {
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> E;
+
+        E -> A Plus;
+
+        [terminal_productions]
+
+        A -> [Token::A];
+
+        Plus -> ["Plus"];
+    );
+    let error = LR1_Parser::try_new(grammar).unwrap_err();
+    assert_eq!(
+        error,
+        GrammarError::UnknownTerminals(vec![String::from("Plus")])
+    );
+}
+
+#[test]
+fn unused_tokens() {
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> Seq;
+
+        Seq -> A B C D E F G H;
+
+        [terminal_productions]
+
+        A -> [Token::A];
+
+        B -> [Token::B];
+
+        C -> [Token::C];
+
+        D -> [Token::D];
+
+        E -> [Token::E];
+
+        F -> [Token::F];
+
+        G -> [Token::G];
+
+        H -> [Token::H];
+    );
+    let parser = LR1_Parser::try_new(grammar).unwrap();
+    assert_eq!(
+        parser.warnings,
+        vec![String::from("token Number is never used by the grammar")]
+    );
+}
+
+#[test]
+fn sentinels_found_by_terminal_id() {
+    //Stop and Oops are displayed like the eof and error variants but are
+    //tokens of their own
+    #[derive(TokenKind, Clone, Debug, PartialEq)]
+    enum Named {
+        Plus,
+        #[display = "end of input"]
+        Stop,
+        #[display = "bad token"]
+        Oops,
+        #[token_kind(error)]
+        #[display = "bad token"]
+        Error,
+        #[token_kind(eof)]
+        #[display = "end of input"]
+        Eof,
+    }
+
+    let grammar: Grammar<AST, Named, TranslatorStack> = grammar!(
+        Start -> Plus;
+
+        [terminal_productions]
+
+        Plus -> [Named::Plus];
+    );
+    let parser = LR1_Parser::try_new(grammar).unwrap();
+    assert_eq!(
+        parser.warnings,
+        vec![
+            String::from("token Stop is never used by the grammar"),
+            String::from("token Oops is never used by the grammar"),
+        ]
+    );
+    let error_ids: Vec<usize> = (0..parser.terminal_table.len())
+        .filter(|id| parser.terminal_table[*id] == Some(crate::symbol::ERROR_SYMBOL_ID))
+        .collect();
+    assert_eq!(error_ids, vec![Named::Error.terminal_id()]);
+}
@@ -0,0 +1,107 @@
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack, AST};
+use crate::token::TokenKind;
+
+#[test]
+fn data_carrying_tokens() {
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> Sum => |sum: i64| -> i64 { sum };
+
+        Sum -> N P N => |l: i64, _p: Token, r: i64| -> i64 { l + r };
+
+        [terminal_productions]
+
+        N -> ["Number"] => |n: Token| -> i64 {
+            match n {
+                Token::Number(n) => n,
+                _ => unreachable!(),
+            }
+        };
+
+        P -> [Token::C] => |c: Token| -> Token { c };
+    );
+    let parser = LR1_Parser::new(grammar);
+    assert_eq!(
+        parser.terminal_table[Token::Number(7).terminal_id()],
+        parser.terminal_table[Token::Number(0).terminal_id()]
+    );
+    let mut errors = Vec::new();
+    let mut ast = AST::new();
+    let value = parser.parse(Token::lexer("1 c 22"), &mut errors, &mut ast);
+    assert!(errors.is_empty());
+    assert_eq!(
+        *value.unwrap().value.unwrap().downcast::<i64>().unwrap(),
+        23
+    );
+}
+
+//a hand-written impl with neither terminal ids nor terminal names, its
+//tokens are matched by their ToString name
+#[derive(Clone, Debug, PartialEq)]
+enum Named {
+    Plus,
+    Number(i64),
+    Error,
+    Eof,
+}
+
+impl std::fmt::Display for Named {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Named::Plus => "Plus",
+            Named::Number(_) => "Number",
+            Named::Error => "Error",
+            Named::Eof => "EOF",
+        })
+    }
+}
+
+impl TokenKind for Named {
+    type TokenKind = Named;
+    fn error() -> Self::TokenKind {
+        Named::Error
+    }
+    fn eof() -> Self::TokenKind {
+        Named::Eof
+    }
+}
+
+#[test]
+fn tokens_matched_by_name() {
+    let grammar: Grammar<AST, Named, TranslatorStack> = grammar!(
+        Start -> Sum => |sum: i64| -> i64 { sum };
+
+        Sum -> N P N => |l: i64, _p: (), r: i64| -> i64 { l + r };
+
+        [terminal_productions]
+
+        N -> ["Number"] => |n: Named| -> i64 {
+            match n {
+                Named::Number(n) => n,
+                _ => unreachable!(),
+            }
+        };
+
+        P -> [Named::Plus];
+    );
+    let parser = LR1_Parser::try_new(grammar).unwrap();
+    assert!(parser.warnings.is_empty());
+    let tokens = vec![
+        (Named::Number(1), 0..1),
+        (Named::Plus, 2..3),
+        (Named::Number(2), 4..5),
+    ];
+    let mut errors = Vec::new();
+    let value = parser.parse(tokens.into_iter(), &mut errors, &mut AST::new());
+    assert_eq!(*value.unwrap().value.unwrap().downcast::<i64>().unwrap(), 3);
+
+    let tokens = vec![(Named::Number(1), 0..1), (Named::Error, 2..3)];
+    let _ = parser.parse(tokens.into_iter(), &mut errors, &mut AST::new());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, crate::error::ParseErrorKind::Lexical);
+    assert_eq!(errors[0].found.as_deref(), Some("Error"));
+    assert_eq!(errors[0].terminal_id, None);
+}
@@ -0,0 +1,52 @@
+use logos::Logos;
+
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{TranslatorStack, AST};
+use crate::token::TokenKind;
+
+#[derive(Logos, TokenKind, Clone, Debug, PartialEq)]
+#[logos(skip r"[ \t\n\r]+")]
+enum Token {
+    #[token("+")]
+    #[display = "'+'"]
+    Plus,
+    #[regex("[0-9]+", |lex| lex.slice().parse().ok())]
+    #[display = "a number"]
+    Number(i64),
+    #[token_kind(error)]
+    Error,
+    #[token_kind(eof)]
+    Eof,
+}
+
+#[test]
+fn token_derive() {
+    assert_eq!(Token::terminal_names(), &["Plus", "Number", "Error", "EOF"]);
+    assert_eq!(Token::Number(7).terminal_id(), 1);
+    assert_eq!(Token::Eof.terminal_id(), 3);
+    assert_eq!(Token::Plus.to_string(), "'+'");
+    assert_eq!(Token::Eof.to_string(), "EOF");
+    assert_eq!(Token::repair_token(0), Some(Token::Plus));
+    assert_eq!(Token::repair_token(1), None);
+
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> E;
+
+        E -> Number Plus Number;
+
+        [terminal_productions]
+
+        Number -> ["Number"];
+
+        Plus -> ["Plus"];
+    );
+    let parser = LR1_Parser::new(grammar);
+    let mut errors = Vec::new();
+    let mut ast = AST::new();
+    parser
+        .parse(Token::lexer("1 2"), &mut errors, &mut ast)
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].message(), "Expected '+' or EOF");
+}
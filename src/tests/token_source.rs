@@ -0,0 +1,198 @@
+use logos::Span;
+
+use crate::error::ParseErrorKind;
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{TranslatorStack, AST};
+use crate::token::TokenKind;
+
+//tokens of a hand-written lexer, no logos involved
+#[derive(TokenKind, Clone, Debug, PartialEq)]
+enum Token {
+    #[display = "'+'"]
+    Plus,
+    #[display = "a number"]
+    Number(i64),
+    #[token_kind(error)]
+    Error,
+    #[token_kind(eof)]
+    Eof,
+}
+
+//splits on whitespace, words that are neither numbers nor '+' are errors
+struct Words<'s> {
+    words: std::str::SplitWhitespace<'s>,
+    source: &'s str,
+}
+
+impl<'s> Words<'s> {
+    fn new(source: &'s str) -> Self {
+        Words {
+            words: source.split_whitespace(),
+            source,
+        }
+    }
+
+    fn span(&self, word: &str) -> Span {
+        let start = word.as_ptr() as usize - self.source.as_ptr() as usize;
+        start..start + word.len()
+    }
+}
+
+impl TokenSource<Token> for Words<'_> {
+    fn next_token(&mut self) -> Option<SourceToken<Token>> {
+        let word = self.words.next()?;
+        let span = self.span(word);
+        Some(match word {
+            "+" => Ok((Token::Plus, span)),
+            _ => match word.parse() {
+                Ok(number) => Ok((Token::Number(number), span)),
+                Err(_) => Err(span),
+            },
+        })
+    }
+
+    fn end(&self) -> Option<usize> {
+        Some(self.source.len())
+    }
+}
+
+fn parser() -> LR1_Parser<AST, Token, TranslatorStack> {
+    LR1_Parser::new(grammar!(
+        Start -> E => |e: i64| -> i64 { e };
+
+        E -> E Plus Number => |e: i64, _plus: (), n: i64| -> i64 { e + n }
+        | Number => |n: i64| -> i64 { n };
+
+        [terminal_productions]
+
+        Number -> ["Number"] => |n: Token| -> i64 {
+            match n {
+                Token::Number(n) => n,
+                _ => unreachable!(),
+            }
+        };
+
+        Plus -> ["Plus"];
+    ))
+}
+
+fn words(input: &str) -> Vec<(Token, Span)> {
+    Words::new(input).map_while(|input| input.ok()).collect()
+}
+
+impl Iterator for Words<'_> {
+    type Item = SourceToken<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token()
+    }
+}
+
+fn sum(outcome: ParseOutcome) -> i64 {
+    *outcome.value.unwrap().downcast::<i64>().unwrap()
+}
+
+#[test]
+fn custom_source() {
+    let mut errors = Vec::new();
+    let outcome = parser().parse(Words::new("1 + 2 + 39"), &mut errors, &mut AST::new());
+    assert!(errors.is_empty());
+    assert_eq!(sum(outcome.unwrap()), 42);
+}
+
+#[test]
+fn custom_source_errors() {
+    let mut errors = Vec::new();
+    let outcome = parser().parse(Words::new("1 + two"), &mut errors, &mut AST::new());
+    assert!(outcome.is_err());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ParseErrorKind::Lexical);
+    assert_eq!(errors[0].span, 4..7);
+    assert_eq!(errors[0].message(), "Invalid token Error");
+
+    //the end of the input is where the source says
+    let mut errors = Vec::new();
+    let _ = parser().parse(Words::new("1 +  "), &mut errors, &mut AST::new());
+    assert_eq!(errors[0].kind, ParseErrorKind::UnexpectedEof);
+    assert_eq!(errors[0].span, 5..5);
+}
+
+#[test]
+fn vec_source() {
+    let mut errors = Vec::new();
+    let tokens = words("1 + 2 + 3");
+    let outcome = parser().parse(tokens.into_iter(), &mut errors, &mut AST::new());
+    assert_eq!(sum(outcome.unwrap()), 6);
+
+    //tokens lexed beforehand can be looked ahead, errors come with repairs
+    let mut errors = Vec::new();
+    let tokens = words("1 + + 2");
+    let _ = parser().parse(tokens.into_iter(), &mut errors, &mut AST::new());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].repair.len(), 1);
+    assert_eq!(errors[0].repair[0].to_string(), "delete 4..5");
+}
+
+#[test]
+fn iterator_source() {
+    let mut errors = Vec::new();
+    let tokens = (1..=4).map(|n| [(Token::Plus, 0..0), (Token::Number(n), 0..0)]);
+    let tokens = std::iter::once((Token::Number(0), 0..0)).chain(tokens.flatten());
+    let outcome = parser().parse(TokenIter(tokens), &mut errors, &mut AST::new());
+    assert_eq!(sum(outcome.unwrap()), 10);
+
+    //the tokens after an error are read ahead once and kept for the parse
+    let mut errors = Vec::new();
+    let tokens = words("1 + + 2 + 3");
+    let read = std::cell::Cell::new(0);
+    let tokens = tokens.into_iter().inspect(|_| read.set(read.get() + 1));
+    let outcome = parser().parse(TokenIter(tokens), &mut errors, &mut AST::new());
+    assert_eq!(read.get(), 6);
+    assert_eq!(outcome.unwrap().stats.tokens, 5);
+    assert_eq!(errors.len(), 1);
+    let repair: Vec<String> = errors[0]
+        .repair
+        .iter()
+        .map(|repair| repair.to_string())
+        .collect();
+    assert_eq!(repair, ["delete 4..5"]);
+}
+
+#[test]
+fn result_iterator_source() {
+    //Words yields SourceTokens, its lexical errors reach the parser
+    let mut errors = Vec::new();
+    let outcome = parser().parse(
+        TokenResults(Words::new("1 + 41")),
+        &mut errors,
+        &mut AST::new(),
+    );
+    assert_eq!(sum(outcome.unwrap()), 42);
+
+    let mut errors = Vec::new();
+    let tokens = TokenResults(Words::new("1 + two"));
+    assert!(parser()
+        .parse(tokens, &mut errors, &mut AST::new())
+        .is_err());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].kind, ParseErrorKind::Lexical);
+    assert_eq!(errors[0].span, 4..7);
+
+    //so do the ones collected beforehand
+    let tokens: Vec<SourceToken<Token>> = Words::new("1 + two").collect();
+    let mut errors = Vec::new();
+    let _ = parser().parse(tokens.clone().into_iter(), &mut errors, &mut AST::new());
+    assert_eq!(errors[0].kind, ParseErrorKind::Lexical);
+    let mut errors = Vec::new();
+    let _ = parser().parse(TokenResults::from(tokens), &mut errors, &mut AST::new());
+    assert_eq!(errors[0].kind, ParseErrorKind::Lexical);
+}
+
+#[test]
+fn from_vec() {
+    let mut errors = Vec::new();
+    let tokens = TokenIter::from(words("1 + 2 + 3"));
+    let outcome = parser().parse(tokens, &mut errors, &mut AST::new());
+    assert_eq!(sum(outcome.unwrap()), 6);
+}
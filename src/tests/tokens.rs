@@ -1,28 +1,93 @@
-#[derive(Clone, Debug, PartialEq)]
+use std::fmt::Display;
+
+use logos::Logos;
+
+use crate::token::TokenKind;
+
+#[derive(Logos, Clone, Debug, PartialEq)]
+#[logos(skip r"[ \t\n\r]+")]
 pub enum Token {
+    #[token("a")]
     A,
+    #[token("b")]
     B,
+    #[token("c")]
     C,
+    #[token("d")]
     D,
+    #[token("e")]
     E,
+    #[token("f")]
     F,
+    #[token("g")]
     G,
+    #[token("h")]
     H,
-    EOF,
+    #[regex("[0-9]+", |lex| lex.slice().parse().ok())]
+    Number(i64),
+    Error,
+    Eof,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Token::A => "A",
+            Token::B => "B",
+            Token::C => "C",
+            Token::D => "D",
+            Token::E => "E",
+            Token::F => "F",
+            Token::G => "G",
+            Token::H => "H",
+            Token::Number(_) => "Number",
+            Token::Error => "ERROR",
+            Token::Eof => "EOF",
+        };
+        f.write_str(name)
+    }
 }
 
-impl ToString for Token {
-    fn to_string(&self) -> String {
+impl TokenKind for Token {
+    type TokenKind = Token;
+    fn error() -> Self::TokenKind {
+        Token::Error
+    }
+    fn eof() -> Self::TokenKind {
+        Token::Eof
+    }
+    fn terminal_id(&self) -> usize {
         match self {
-            Token::A => String::from("A"),
-            Token::B => String::from("B"),
-            Token::C => String::from("C"),
-            Token::D => String::from("D"),
-            Token::E => String::from("E"),
-            Token::F => String::from("F"),
-            Token::G => String::from("G"),
-            Token::H => String::from("H"),
-            Token::EOF => String::from("EOF"),
+            Token::A => 0,
+            Token::B => 1,
+            Token::C => 2,
+            Token::D => 3,
+            Token::E => 4,
+            Token::F => 5,
+            Token::G => 6,
+            Token::H => 7,
+            Token::Number(_) => 8,
+            Token::Error => 9,
+            Token::Eof => 10,
         }
     }
+    fn terminal_names() -> &'static [&'static str] {
+        &[
+            "A", "B", "C", "D", "E", "F", "G", "H", "Number", "ERROR", "EOF",
+        ]
+    }
+    fn repair_token(terminal_id: usize) -> Option<Self> {
+        [
+            Token::A,
+            Token::B,
+            Token::C,
+            Token::D,
+            Token::E,
+            Token::F,
+            Token::G,
+            Token::H,
+        ]
+        .get(terminal_id)
+        .cloned()
+    }
 }
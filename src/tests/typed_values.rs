@@ -0,0 +1,119 @@
+use logos::Logos;
+
+use crate::error::GrammarError;
+use crate::grammar;
+use crate::prelude::*;
+use crate::tests::{tokens::Token, TranslatorStack, AST};
+
+#[test]
+fn typed_values() {
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> E => |e: String| -> String { e };
+
+        E -> E P T => |e: String, op: String, t: String| -> String { format!("({e}{op}{t})") }
+        | T => |t: String| -> String { t };
+
+        [terminal_productions]
+
+        P -> [Token::C] => |_c: Token| -> String { String::from("+") };
+
+        T -> [Token::A] => |a: Token| -> String { a.to_string() }
+        | [Token::B] => |b: Token| -> String { b.to_string() };
+    );
+    let parser = LR1_Parser::new(grammar);
+    let mut errors = Vec::new();
+    let mut ast = AST::new();
+    let value = parser.parse(Token::lexer("a c b c a"), &mut errors, &mut ast);
+    assert!(errors.is_empty());
+    let value = value.unwrap().value.unwrap().downcast::<String>().unwrap();
+    assert_eq!(*value, "((A+B)+A)");
+}
+
+#[test]
+fn mismatched_value_types() {
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> E;
+
+        E -> T => |_t: ()| -> String { String::new() }
+        | T T => |_t: (), _u: ()| -> usize { 0 };
+
+        [terminal_productions]
+
+        T -> [Token::A];
+    );
+    let error = LR1_Parser::try_new(grammar).unwrap_err();
+    assert_eq!(
+        error,
+        GrammarError::MismatchedValueTypes(vec![String::from(
+            "E is declared with value type String and usize"
+        )])
+    );
+}
+
+#[test]
+fn mismatched_argument_types() {
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> Sum => |sum: usize| -> usize { sum };
+
+        Sum -> Sum P T => |sum: String, _p: String, t: String| -> String { sum + &t }
+        | T => |t: String| -> String { t };
+
+        [terminal_productions]
+
+        P -> [Token::C] => |c: Token| -> Token { c };
+
+        T -> [Token::A] => |a: String| -> String { a }
+        | [Token::B];
+    );
+    let error = LR1_Parser::try_new(grammar).unwrap_err();
+    assert_eq!(
+        error,
+        GrammarError::MismatchedValueTypes(vec![
+            String::from(
+                "argument 1 of the typed action of Start -> Sum is usize but Sum has value type String"
+            ),
+            String::from(
+                "argument 2 of the typed action of Sum -> Sum P T is String but P has value type Token"
+            ),
+            String::from(
+                "argument 1 of the typed action of T -> A is String but A has value type Token"
+            ),
+            String::from("T -> B has no typed action but T has value type String"),
+        ])
+    );
+}
+
+#[test]
+fn typed_values_checked_before_parsing() {
+    //values of nonterminals without typed actions and of mid-rule actions are ()
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> U { |_ctx| {} } T => |_u: (), _m: (), t: String| -> String { t };
+
+        [terminal_productions]
+
+        U -> [Token::A];
+        T -> [Token::B] => |b: crate::tests::tokens::Token| -> String { b.to_string() };
+    );
+    assert!(LR1_Parser::try_new(grammar).is_ok());
+}
+
+#[test]
+fn value_types_compared_by_type() {
+    //the same types written with and without their path
+    let grammar: Grammar<AST, Token, TranslatorStack> = grammar!(
+        Start -> L => |l: Vec<crate::tests::tokens::Token>| -> usize { l.len() };
+
+        L -> L T => |l: Vec<Token>, t: Token| -> Vec<Token> { [l, vec![t]].concat() }
+        | T => |t: Token| -> std::vec::Vec<Token> { vec![t] };
+
+        [terminal_productions]
+
+        T -> [Token::A] => |a: crate::tests::tokens::Token| -> Token { a };
+    );
+    let parser = LR1_Parser::try_new(grammar).unwrap();
+    let mut errors = Vec::new();
+    let mut ast = AST::new();
+    let value = parser.parse(Token::lexer("a a a"), &mut errors, &mut ast);
+    let value = value.unwrap().value.unwrap().downcast::<usize>().unwrap();
+    assert_eq!(*value, 3);
+}
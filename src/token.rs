@@ -1,9 +1,92 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, ops::Deref};
+
+use logos::Span;
+
+pub use manodae_derive::TokenKind;
 
 pub trait TokenKind: ToString + Debug + Clone {
-    type TokenKind: ToString;
+    ///kind of the sentinels, usually Self. The parser turns them into tokens
+    ///for lexer errors and the end of the input.
+    type TokenKind: ToString + Into<Self>;
     ///returns an developer intended error token which is used while parsing
     fn error() -> Self::TokenKind;
     ///returns an developer intended end of the file token
     fn eof() -> Self::TokenKind;
+    ///returns a stable id of the token's variant, ids are dense starting at 0
+    ///and do not depend on the data a variant carries. Defaults to the
+    ///position of the token's name in terminal_names.
+    fn terminal_id(&self) -> usize {
+        let name = self.to_string();
+        Self::terminal_names()
+            .iter()
+            .position(|terminal| *terminal == name)
+            .unwrap_or(usize::MAX)
+    }
+    ///returns the grammar terminal name of every variant, indexed by
+    ///terminal_id. Empty by default, tokens are then matched to terminals by
+    ///their ToString name like before terminal ids existed.
+    fn terminal_names() -> &'static [&'static str] {
+        &[]
+    }
+    ///returns the name of every variant shown in "Expected ..." messages,
+    ///indexed by terminal_id
+    fn terminal_display_names() -> &'static [&'static str] {
+        Self::terminal_names()
+    }
+    ///returns the token of the variant `terminal_id` that an applied repair
+    ///inserts, None when the variant carries data. Repairs inserting a
+    ///terminal with no token are not applied. None by default.
+    fn repair_token(_terminal_id: usize) -> Option<Self> {
+        None
+    }
+}
+
+///Names a grammar terminal, either directly or through a token's variant
+pub trait TerminalName {
+    fn terminal_name(&self) -> String;
+}
+
+impl TerminalName for &str {
+    fn terminal_name(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl TerminalName for String {
+    fn terminal_name(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<T: TokenKind> TerminalName for T {
+    fn terminal_name(&self) -> String {
+        T::terminal_names()
+            .get(self.terminal_id())
+            .map_or_else(|| self.to_string(), |name| name.to_string())
+    }
+}
+
+///Tokens and spans matched by the body of the production being reduced,
+///handed to its semantic action. Derefs to the tokens shifted directly by
+///the body, nonterminals in the body only contribute their span.
+#[derive(Debug)]
+pub struct ProductionTokens<'a, Token> {
+    pub tokens: &'a [(Token, Span)],
+    ///span of every body symbol, like @1..@n in yacc
+    pub spans: &'a [Span],
+    ///span of the whole production, like @$ in yacc
+    pub span: Span,
+}
+
+impl<Token> ProductionTokens<'_, Token> {
+    pub fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+impl<Token> Deref for ProductionTokens<'_, Token> {
+    type Target = [(Token, Span)];
+    fn deref(&self) -> &Self::Target {
+        self.tokens
+    }
 }
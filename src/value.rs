@@ -0,0 +1,63 @@
+use std::{
+    any::{type_name, Any, TypeId},
+    fmt::Display,
+};
+
+///Semantic value of a grammar symbol kept on the parser's value stack.
+///Terminals carry the shifted token, nonterminals carry whatever their
+///production's typed action returned (or `()` when there is none).
+pub type SemanticValue = Box<dyn Any>;
+
+///Type of a semantic value as written in a typed action. Types are compared
+///by their TypeId, the name is kept for messages.
+#[derive(Debug, Clone, Eq)]
+pub struct ValueType {
+    pub name: String,
+    pub id: TypeId,
+}
+
+impl ValueType {
+    pub fn of<T: 'static>(name: &str) -> Self {
+        ValueType {
+            name: name.replace(' ', ""),
+            id: TypeId::of::<T>(),
+        }
+    }
+}
+
+impl PartialEq for ValueType {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Display for ValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+///Takes the next right-hand side value and downcasts it to `T`,
+///used by the typed actions generated by the grammar macros. A `T` of
+///`SemanticValue` takes the value without downcasting it. Err when the value
+///is missing or of another type, the parser reports it as a semantic error.
+pub fn take_value<T: 'static>(
+    values: &mut impl Iterator<Item = SemanticValue>,
+    head: &str,
+) -> Result<T, String> {
+    let value = values
+        .next()
+        .ok_or_else(|| format!("missing semantic value in production of {head}"))?;
+    //untyped arguments take the value as it is
+    if TypeId::of::<T>() == TypeId::of::<SemanticValue>() {
+        let value: SemanticValue = Box::new(value);
+        return Ok(*value.downcast::<T>().unwrap());
+    }
+    match value.downcast::<T>() {
+        Ok(value) => Ok(*value),
+        Err(_) => Err(format!(
+            "semantic value in production of {head} is not of type {}",
+            type_name::<T>()
+        )),
+    }
+}